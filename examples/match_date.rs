@@ -1,4 +1,6 @@
-use human_regex::{beginning, digit, end, exactly, text};
+use human_regex::{
+    beginning, digit, end, exactly, number_in_range, number_in_range_with, text, NumberOptions,
+};
 
 fn main() {
     // Build the first match pattern
@@ -14,9 +16,26 @@ fn main() {
     let regex_string_2 =
         beginning() + exactly(4, digit()) + exactly(2, text("-") + exactly(2, digit())) + end();
 
+    // Build a third match pattern that only accepts real months and days
+    let two_digits = NumberOptions {
+        width: Some(2),
+        ..Default::default()
+    };
+    let regex_string_3 = beginning()
+        + number_in_range(1000..=9999)
+        + text("-")
+        + number_in_range_with(1..=12, two_digits)
+        + text("-")
+        + number_in_range_with(1..=31, two_digits)
+        + end();
+
     // Check the match
     println!("{}", regex_string_1.to_regex().is_match("2014-01-01"));
 
     // Check the match
     println!("{}", regex_string_2.to_regex().is_match("2014-01-01"));
+
+    // Check the match, and that month 99 is rejected
+    println!("{}", regex_string_3.to_regex().is_match("2014-01-01"));
+    println!("{}", regex_string_3.to_regex().is_match("2014-99-01"));
 }
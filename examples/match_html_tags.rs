@@ -1,4 +1,5 @@
-use human_regex::{any, one_or_more, text};
+use human_regex::patterns::markup::open_tag;
+use human_regex::{any, balanced, one_or_more, text, without_set};
 
 fn main() {
     // Define a string to match against
@@ -21,4 +22,27 @@ fn main() {
     {
         println!("Lazy: {}", &capture[0]);
     }
+
+    // Neither can pair up nested tags, but a balanced match up to a fixed depth can
+    let nested_string = "<div>outer <div>inner</div> outer</div><div>next</div>";
+    let lazy_div = text("<div>") + one_or_more(any()).lazy() + text("</div>");
+    for capture in lazy_div.to_regex().captures_iter(nested_string) {
+        println!("Lazy div: {}", &capture[0]);
+    }
+    let balanced_div = balanced("<div>", "</div>", 3, without_set(&["<"]));
+    for capture in balanced_div.to_regex().captures_iter(nested_string) {
+        println!("Balanced div: {}", &capture[0]);
+    }
+
+    // A lazy match also stops at a `>` inside an attribute value, which the markup patterns skip
+    let attribute_string = r#"<p title="1 > 0">True</p>"#;
+    for capture in greedy_regex_string
+        .to_regex()
+        .captures_iter(attribute_string)
+    {
+        println!("Lazy tag: {}", &capture[0]);
+    }
+    for capture in open_tag(None).to_regex().captures_iter(attribute_string) {
+        println!("Markup tag: {}", &capture[0]);
+    }
 }
@@ -0,0 +1,31 @@
+use human_regex::patterns::network::{cidr_v4_with, ipv6, mac_address_with};
+use human_regex::patterns::Naming;
+use human_regex::{named_capture, or, text, word_boundary};
+
+fn main() {
+    // Define a few lines of a firewall log to play with
+    let log = "\
+    allow 10.0.0.0/8 from 00:1a:2b:3c:4d:5e\n\
+    deny 2001:db8::7 from 00:1a:2b:3c:4d:5f\n\
+    deny 192.168.300.0/24 from 00:1a:2b:3c:4d:60\n\
+    ";
+
+    // Prefixes keep the capture names apart when several patterns are combined
+    let source = or(&[
+        cidr_v4_with(Naming::Prefixed("block")),
+        named_capture(ipv6(), "host"),
+    ]);
+    let regex_string = word_boundary()
+        + source
+        + text(" from ")
+        + mac_address_with(':', Naming::Prefixed("device"));
+
+    // Octets above 255 are rejected by the regex itself
+    for caps in regex_string.to_regex().captures_iter(log) {
+        match caps.name("block_address") {
+            Some(address) => println!("block {} /{}", address.as_str(), &caps["block_prefix"]),
+            None => println!("host {}", &caps["host"]),
+        }
+        println!("  device ending in {}", &caps["device_octet6"]);
+    }
+}
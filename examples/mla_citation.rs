@@ -23,29 +23,29 @@ fn main() {
     let last_author =
         zero_or_more(text(", and ") + one_or_more(word()) + text(" ") + one_or_more(word())).lazy();
 
-    let mla_authors = named_capture(
+    let authors = named_capture(
         first_author + middle_authors + last_author + text(". "),
         "authors",
     );
 
-    // The rest is easy
-    let mla_title =
-        text("\"") + named_capture(one_or_more(nonescaped_text("[^\"]")), "title") + text(".\" ");
-    let mla_journal = named_capture(one_or_more(one_or_more(word()) + text(" ")), "journal");
-    let mla_volume = zero_or_one(named_capture(one_or_more(digit()), "volume"));
-    let mla_year = zero_or_one(text(" (") + named_capture(exactly(4, digit()), "year") + text(")"));
-    let mla_pp = zero_or_one(
-        text(": ")
-            + named_capture(one_or_more(digit()), "lower_page")
-            + text("-")
-            + named_capture(one_or_more(digit()), "upper_page"),
-    );
+    // The rest is easy, and each capture group is kept so that it can be looked up later
+    let title = named_capture(one_or_more(nonescaped_text("[^\"]")), "title");
+    let journal = named_capture(one_or_more(one_or_more(word()) + text(" ")), "journal");
+    let volume = named_capture(one_or_more(digit()), "volume");
+    let year = named_capture(exactly(4, digit()), "year");
+    let lower_page = named_capture(one_or_more(digit()), "lower_page");
+    let upper_page = named_capture(one_or_more(digit()), "upper_page");
+
+    let mla_title = text("\"") + &title + text(".\" ");
+    let mla_volume = zero_or_one(volume.clone());
+    let mla_year = zero_or_one(text(" (") + &year + text(")"));
+    let mla_pp = zero_or_one(text(": ") + &lower_page + text("-") + &upper_page);
 
     // Combine independent pieces
     let mla_regex = one_or_more(
-        mla_authors
+        &authors
             + mla_title
-            + mla_journal
+            + &journal
             + mla_volume
             + mla_year
             + mla_pp
@@ -54,24 +54,21 @@ fn main() {
     )
     .lazy();
 
-    // Return matches
-    for capture in mla_regex.to_regex().captures_iter(citations_in_mla) {
-        println!("Full citation: {}", &capture[0]);
-        println!("\t- authors: {}", &capture[1]);
-        println!("\t- title: {}", &capture[2]);
-        println!("\t- journal: {}", &capture[3]);
-        println!(
-            "\t- volume: {}",
-            &capture.get(4).map_or("N/A", |x| { x.as_str() }),
-        );
-        println!(
-            "\t- year: {}",
-            &capture.get(5).map_or("N/A", |x| { x.as_str() }),
-        );
+    // Return matches, looking up each group by the pattern that made it
+    fn or_na(found: Option<regex::Match<'_>>) -> &str {
+        found.map_or("N/A", |x| x.as_str())
+    }
+    for capture in mla_regex.captures_iter(citations_in_mla) {
+        println!("Full citation: {}", capture.whole().as_str());
+        println!("\t- authors: {}", or_na(capture.get(&authors)));
+        println!("\t- title: {}", or_na(capture.get(&title)));
+        println!("\t- journal: {}", or_na(capture.get(&journal)));
+        println!("\t- volume: {}", or_na(capture.get(&volume)));
+        println!("\t- year: {}", or_na(capture.get(&year)));
         println!(
             "\t- pages: from {} to {}",
-            &capture.get(6).map_or("N/A", |x| { x.as_str() }),
-            &capture.get(7).map_or("N/A", |x| { x.as_str() }),
+            or_na(capture.get(&lower_page)),
+            or_na(capture.get(&upper_page)),
         );
     }
 }
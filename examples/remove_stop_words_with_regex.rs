@@ -1,4 +1,6 @@
-use human_regex::{escape_all, exactly, one_or_more, or, punctuation, whitespace, word_boundary};
+use human_regex::{
+    escape_all, exactly, literal, one_or_more, or, punctuation, whitespace, word_boundary, Rewriter,
+};
 use stop_words::{get, LANGUAGE};
 
 fn main() {
@@ -11,22 +13,20 @@ fn main() {
     // Get the stopwords
     let words = get(LANGUAGE::English);
 
-    // Remove punctuation and lowercase the text to make parsing easier
+    // Lowercase the text to make parsing easier
     let lowercase_doc = document.to_ascii_lowercase();
-    let regex_for_punctuation = one_or_more(punctuation());
-    let text_without_punctuation = regex_for_punctuation
-        .to_regex()
-        .replace_all(&*lowercase_doc, "");
 
-    // Make a regex to match stopwords with trailing spaces and punctuation
+    // Make a regex to match stopwords with trailing spaces
     let regex_for_stop_words = word_boundary()
         + exactly(1, or(&escape_all(&words)))
         + word_boundary()
         + one_or_more(whitespace());
 
-    // Remove stop words
-    let clean_text = regex_for_stop_words
-        .to_regex()
-        .replace_all(&*text_without_punctuation, "");
+    // Remove punctuation and stop words in a single pass
+    let clean_text = Rewriter::new()
+        .rule(one_or_more(punctuation()), literal(""))
+        .rule(regex_for_stop_words, literal(""))
+        .rewrite(&lowercase_doc)
+        .unwrap();
     println!("\nClean text:\n{}", clean_text);
 }
@@ -1,7 +1,10 @@
 //! Functions for capturing matches
 
 use super::humanregex::*;
+use regex::{Captures, Match, Regex};
+use regex_syntax::ast::{self, Ast, GroupKind};
 use std::marker::PhantomData as pd;
+use std::sync::Arc;
 
 /// Add a numbered capturing group around an expression
 /// ```
@@ -18,7 +21,6 @@ use std::marker::PhantomData as pd;
 /// assert_eq!("03", caps.get(2).unwrap().as_str());
 /// assert_eq!("14", caps.get(3).unwrap().as_str());
 /// ```
-
 pub fn capture<T>(target: HumanRegex<T>) -> HumanRegex<SymbolChain> {
     HumanRegex(format!("({})", target), pd::<SymbolChain>)
 }
@@ -40,3 +42,239 @@ pub fn capture<T>(target: HumanRegex<T>) -> HumanRegex<SymbolChain> {
 pub fn named_capture<T>(target: HumanRegex<T>, name: &str) -> HumanRegex<SymbolChain> {
     HumanRegex(format!("(?P<{}>{})", name, target), pd::<SymbolChain>)
 }
+
+/// The capture groups of a match, which can be looked up by the [HumanRegex] that made them rather
+/// than by name or number
+/// ```
+/// use human_regex::{capture, digit, exactly, named_capture, text};
+/// let year = named_capture(exactly(4, digit()), "year");
+/// let month = capture(exactly(2, digit()));
+/// let regex_string = &year + text("-") + &month;
+///
+/// let caps = regex_string.captures("Released 2010-03").unwrap();
+/// assert_eq!(caps.get(&year).unwrap().as_str(), "2010");
+/// assert_eq!(caps.get(&month).unwrap().as_str(), "03");
+/// assert_eq!(caps.whole().as_str(), "2010-03");
+/// ```
+#[derive(Debug)]
+pub struct HumanCaptures<'h> {
+    /// The captures found by the regex crate
+    captures: Captures<'h>,
+    /// The text of every capture group in the regex string, with its index
+    groups: Arc<[(String, usize)]>,
+}
+
+impl<'h> HumanCaptures<'h> {
+    /// The match of a capture group made by [capture] or [named_capture], or `None` if the group
+    /// did not take part in the match or is not in the regex at all.
+    ///
+    /// Groups are recognized by their regex string, so two groups made by [capture] around the same
+    /// target cannot be told apart, and looking either of them up gives `None`. Give such groups
+    /// different names with [named_capture] instead.
+    /// ```
+    /// use human_regex::{capture, digit, named_capture, one_or_more, text};
+    /// let first = capture(one_or_more(digit()));
+    /// let second = capture(one_or_more(digit()));
+    /// let caps = (&first + text("-") + &second).captures("1-2").unwrap();
+    /// assert!(caps.get(&first).is_none());
+    /// assert!(caps.get(&second).is_none());
+    ///
+    /// let first = named_capture(one_or_more(digit()), "first");
+    /// let second = named_capture(one_or_more(digit()), "second");
+    /// let caps = (&first + text("-") + &second).captures("1-2").unwrap();
+    /// assert_eq!(caps.get(&second).unwrap().as_str(), "2");
+    /// ```
+    pub fn get<T>(&self, group: &HumanRegex<T>) -> Option<Match<'h>> {
+        match group_indices(&self.groups, &group.0).as_slice() {
+            [index] => self.captures.get(*index),
+            _ => None,
+        }
+    }
+
+    /// The whole match
+    pub fn whole(&self) -> Match<'h> {
+        self.captures.get(0).unwrap()
+    }
+
+    /// The captures as the regex crate found them, for looking groups up by name or number
+    pub fn inner(&self) -> &Captures<'h> {
+        &self.captures
+    }
+}
+
+impl<T> HumanRegex<T> {
+    /// The capture groups of the leftmost match in the haystack, if there is one. To search more
+    /// than once, compile the regex once with [to_capturing_regex](HumanRegex::to_capturing_regex).
+    pub fn captures<'h>(&self, haystack: &'h str) -> Option<HumanCaptures<'h>> {
+        self.to_capturing_regex().captures(haystack)
+    }
+
+    /// The capture groups of every successive non-overlapping match in the haystack, found one at
+    /// a time as the iterator advances
+    /// ```
+    /// use human_regex::{digit, named_capture, one_or_more, text, word};
+    /// let key = named_capture(one_or_more(word()), "key");
+    /// let value = named_capture(one_or_more(digit()), "value");
+    /// let regex_string = &key + text("=") + &value;
+    /// let pairs: Vec<(&str, &str)> = regex_string
+    ///     .captures_iter("a=1 b=22")
+    ///     .map(|caps| (caps.get(&key).unwrap().as_str(), caps.get(&value).unwrap().as_str()))
+    ///     .collect();
+    /// assert_eq!(pairs, vec![("a", "1"), ("b", "22")]);
+    /// ```
+    pub fn captures_iter<'h>(&self, haystack: &'h str) -> HumanCapturesIter<'h> {
+        self.to_capturing_regex().captures_iter(haystack)
+    }
+
+    /// Compile the regex along with the capture groups in it, so that it can be searched many
+    /// times without being compiled again
+    /// ```
+    /// use human_regex::{digit, named_capture, one_or_more};
+    /// let number = named_capture(one_or_more(digit()), "number");
+    /// let capturing = number.to_capturing_regex();
+    /// for (haystack, expected) in [("a1", "1"), ("b22", "22")] {
+    ///     assert_eq!(capturing.captures(haystack).unwrap().get(&number).unwrap().as_str(), expected);
+    /// }
+    /// ```
+    pub fn to_capturing_regex(&self) -> CapturingRegex {
+        CapturingRegex {
+            regex: self.to_regex(),
+            groups: self.capture_groups(),
+        }
+    }
+
+    /// The text of every capture group in the regex string, with its index, in the order they
+    /// appear
+    pub(crate) fn capture_groups(&self) -> Arc<[(String, usize)]> {
+        let parsed = ast::parse::Parser::new().parse(&self.0).unwrap();
+        let mut groups = Vec::new();
+        collect_groups(&parsed, &self.0, &mut groups);
+        groups.into()
+    }
+}
+
+/// A compiled regex whose matches can have their capture groups looked up by the [HumanRegex] that
+/// made them, made by [HumanRegex::to_capturing_regex]
+#[derive(Debug, Clone)]
+pub struct CapturingRegex {
+    /// The compiled regex
+    regex: Regex,
+    /// The text of every capture group in the regex string, with its index
+    groups: Arc<[(String, usize)]>,
+}
+
+impl CapturingRegex {
+    /// The compiled regex, for searches that do not need the capture groups
+    pub fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    /// The capture groups of the leftmost match in the haystack, if there is one
+    pub fn captures<'h>(&self, haystack: &'h str) -> Option<HumanCaptures<'h>> {
+        self.regex.captures(haystack).map(|captures| HumanCaptures {
+            captures,
+            groups: Arc::clone(&self.groups),
+        })
+    }
+
+    /// The capture groups of every successive non-overlapping match in the haystack, found one at
+    /// a time as the iterator advances
+    /// ```
+    /// use human_regex::{capture, digit, one_or_more};
+    /// let number = capture(one_or_more(digit()));
+    /// let capturing = number.to_capturing_regex();
+    /// let mut numbers = capturing.captures_iter("1 22 333");
+    /// assert_eq!(numbers.next().unwrap().get(&number).unwrap().as_str(), "1");
+    /// assert_eq!(numbers.next().unwrap().get(&number).unwrap().as_str(), "22");
+    /// ```
+    pub fn captures_iter<'h>(&self, haystack: &'h str) -> HumanCapturesIter<'h> {
+        HumanCapturesIter {
+            regex: self.regex.clone(),
+            groups: Arc::clone(&self.groups),
+            haystack,
+            start: Some(0),
+            last_end: None,
+        }
+    }
+}
+
+/// An iterator over the capture groups of successive non-overlapping matches, made by
+/// [HumanRegex::captures_iter] and [CapturingRegex::captures_iter]
+#[derive(Debug, Clone)]
+pub struct HumanCapturesIter<'h> {
+    /// The compiled regex
+    regex: Regex,
+    /// The text of every capture group in the regex string, with its index
+    groups: Arc<[(String, usize)]>,
+    /// The text being searched
+    haystack: &'h str,
+    /// Where the next search starts, or `None` once the end of the text has been passed
+    start: Option<usize>,
+    /// The end of the previous match
+    last_end: Option<usize>,
+}
+
+impl<'h> Iterator for HumanCapturesIter<'h> {
+    type Item = HumanCaptures<'h>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let captures = self.regex.captures_at(self.haystack, self.start?)?;
+            let whole = captures.get(0).unwrap();
+            // Like the regex crate, skip an empty match right after the previous match
+            self.start = if whole.is_empty() {
+                self.haystack[whole.end()..]
+                    .chars()
+                    .next()
+                    .map(|c| whole.end() + c.len_utf8())
+            } else {
+                Some(whole.end())
+            };
+            if whole.is_empty() && self.last_end == Some(whole.end()) {
+                continue;
+            }
+            self.last_end = Some(whole.end());
+            return Some(HumanCaptures {
+                captures,
+                groups: Arc::clone(&self.groups),
+            });
+        }
+    }
+}
+
+/// The indices of the capture groups with the same regex string as a handle
+pub(crate) fn group_indices(groups: &[(String, usize)], text: &str) -> Vec<usize> {
+    groups
+        .iter()
+        .filter(|(group, _)| group == text)
+        .map(|&(_, index)| index)
+        .collect()
+}
+
+/// Find the capture groups in a parsed regex, in the order they appear
+fn collect_groups(node: &Ast, pattern: &str, groups: &mut Vec<(String, usize)>) {
+    match node {
+        Ast::Group(group) => {
+            let index = match &group.kind {
+                GroupKind::CaptureIndex(index) => Some(*index),
+                GroupKind::CaptureName { name, .. } => Some(name.index),
+                GroupKind::NonCapturing(_) => None,
+            };
+            if let Some(index) = index {
+                let text = &pattern[group.span.start.offset..group.span.end.offset];
+                groups.push((text.to_string(), index as usize));
+            }
+            collect_groups(&group.ast, pattern, groups);
+        }
+        Ast::Repetition(repetition) => collect_groups(&repetition.ast, pattern, groups),
+        Ast::Alternation(alternation) => alternation
+            .asts
+            .iter()
+            .for_each(|option| collect_groups(option, pattern, groups)),
+        Ast::Concat(concat) => concat
+            .asts
+            .iter()
+            .for_each(|part| collect_groups(part, pattern, groups)),
+        _ => {}
+    }
+}
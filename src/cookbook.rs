@@ -15,3 +15,7 @@
 //! ```rust
 #![doc = include_str ! ("../examples/mla_citation.rs")]
 //! ```
+//! ## Matching IP and MAC Addresses
+//! ```rust
+#![doc = include_str ! ("../examples/match_network_addresses.rs")]
+//! ```
@@ -0,0 +1,773 @@
+//! Exporting patterns to other regex dialects
+//!
+//! [HumanRegex] renders Rust `regex` syntax, but the same pattern often needs to run elsewhere.
+//! [HumanRegex::to_dialect] rewrites a pattern for another engine, keeping its meaning. Constructs
+//! that the target lacks are translated or expanded. Class set operations become explicit ranges,
+//! ASCII classes and Unicode categories are spelled out where there is no shorthand, and flag groups
+//! become case-folded classes or anchors written with lookarounds. When no equivalent exists, such
+//! as a lazy quantifier in POSIX, a [DialectError] is returned instead.
+//! ```
+//! use human_regex::{named_capture, one_or_more, text, within_range, xor, Dialect};
+//! let regex_string = named_capture(one_or_more(xor(within_range('a'..='g'), within_range('b'..='h'))), "ends")
+//!     + text(".");
+//! assert_eq!(regex_string.to_dialect(Dialect::EcmaScript).unwrap(), r"(?<ends>(?:[ah])+)(?:\.)");
+//! assert_eq!(regex_string.to_dialect(Dialect::Python).unwrap(), r"(?P<ends>(?:[ah])+)(?:\.)");
+//! assert_eq!(regex_string.to_dialect(Dialect::Posix).unwrap(), r"([ah]+)\.");
+//! ```
+
+use super::humanregex::*;
+use regex_syntax::ast::{
+    self, AssertionKind, Ast, ClassAsciiKind, ClassPerlKind, ClassSet, ClassSetItem,
+    ClassUnicodeKind, Flag, FlagsItemKind, GroupKind, RepetitionKind, RepetitionRange,
+};
+use regex_syntax::hir::{self, translate::TranslatorBuilder, Class, HirKind};
+
+/// The regex dialects that patterns can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    /// JavaScript regular expressions. The pattern must be compiled with the `u` flag, as in
+    /// `new RegExp(pattern, "u")`.
+    EcmaScript,
+    /// Perl Compatible Regular Expressions (PCRE2), as used by nginx and PHP. Patterns that depend
+    /// on Unicode classes start with `(*UTF)(*UCP)`.
+    Pcre,
+    /// Python's `re` module, for `str` patterns
+    Python,
+    /// POSIX extended regular expressions, as used by `grep -E`, in a UTF-8 locale. POSIX has no
+    /// non-capturing groups, so groups that are needed for grouping also capture and group numbers
+    /// can differ from the original.
+    Posix,
+}
+
+impl fmt::Display for Dialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Dialect::EcmaScript => "ECMAScript",
+                Dialect::Pcre => "PCRE",
+                Dialect::Python => "Python",
+                Dialect::Posix => "POSIX",
+            }
+        )
+    }
+}
+
+/// An error for a pattern that cannot be expressed in the requested dialect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialectError {
+    /// The dialect that was requested
+    pub dialect: Dialect,
+    /// A description of what could not be exported
+    pub message: String,
+}
+
+impl fmt::Display for DialectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot export to {}: {}", self.dialect, self.message)
+    }
+}
+
+impl std::error::Error for DialectError {}
+
+impl<T> HumanRegex<T> {
+    /// Render the pattern in another regex dialect
+    /// ```
+    /// use human_regex::{case_insensitive, digit, one_or_more, text, word_boundary, Dialect};
+    /// let regex_string = word_boundary() + case_insensitive(text("id")) + one_or_more(digit());
+    /// assert_eq!(
+    ///     regex_string.to_dialect(Dialect::Pcre).unwrap(),
+    ///     r"(*UTF)(*UCP)\b(?i:(?:id))(?:\d)+"
+    /// );
+    /// assert_eq!(
+    ///     regex_string.to_dialect(Dialect::Posix).unwrap_err().to_string(),
+    ///     "cannot export to POSIX: word boundaries are not supported"
+    /// );
+    ///
+    /// let regex_string = case_insensitive(text("id")) + one_or_more(digit());
+    /// assert_eq!(
+    ///     regex_string.to_dialect(Dialect::EcmaScript).unwrap(),
+    ///     r"(?:(?:[Ii][Dd]))(?:\p{Nd})+"
+    /// );
+    /// ```
+    pub fn to_dialect(&self, dialect: Dialect) -> Result<String, DialectError> {
+        let error = |message: String| DialectError { dialect, message };
+        let parsed = ast::parse::Parser::new()
+            .parse(&self.0)
+            .map_err(|e| error(e.to_string()))?;
+        let exporter = Exporter {
+            dialect,
+            pattern: &self.0,
+            needs_ucp: std::cell::Cell::new(false),
+        };
+        let rendered = exporter
+            .render(&parsed, Flags::default(), false)
+            .map_err(error)?;
+        Ok(if exporter.needs_ucp.get() {
+            format!("(*UTF)(*UCP){}", rendered)
+        } else {
+            rendered
+        })
+    }
+}
+
+/// The flags in effect at some point in a pattern
+#[derive(Debug, Clone, Copy, Default)]
+struct Flags {
+    /// `i`
+    case_insensitive: bool,
+    /// `m`
+    multi_line: bool,
+    /// `s`
+    dot_matches_new_line: bool,
+    /// `U`
+    swap_greed: bool,
+    /// `-u`
+    ascii: bool,
+}
+
+impl Flags {
+    /// Apply the items of a flag group such as `(?i-s)`
+    fn update(mut self, flags: &ast::Flags) -> Result<Self, String> {
+        let mut enable = true;
+        for item in &flags.items {
+            match item.kind {
+                FlagsItemKind::Negation => enable = false,
+                FlagsItemKind::Flag(Flag::CaseInsensitive) => self.case_insensitive = enable,
+                FlagsItemKind::Flag(Flag::MultiLine) => self.multi_line = enable,
+                FlagsItemKind::Flag(Flag::DotMatchesNewLine) => self.dot_matches_new_line = enable,
+                FlagsItemKind::Flag(Flag::SwapGreed) => self.swap_greed = enable,
+                FlagsItemKind::Flag(Flag::Unicode) => self.ascii = !enable,
+                // Whitespace has already been dropped while parsing
+                FlagsItemKind::Flag(Flag::IgnoreWhitespace) => {}
+                FlagsItemKind::Flag(Flag::CRLF) => {
+                    return Err("CRLF mode is not supported".to_string())
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// The `i`, `m` and `s` flags that differ from another set, as written in a flag group
+    fn difference(&self, outer: &Flags) -> String {
+        let mut on = String::new();
+        let mut off = String::new();
+        for (flag, inner, outer) in [
+            ('i', self.case_insensitive, outer.case_insensitive),
+            ('m', self.multi_line, outer.multi_line),
+            ('s', self.dot_matches_new_line, outer.dot_matches_new_line),
+        ] {
+            if inner && !outer {
+                on.push(flag);
+            } else if !inner && outer {
+                off.push(flag);
+            }
+        }
+        if off.is_empty() {
+            on
+        } else {
+            format!("{}-{}", on, off)
+        }
+    }
+}
+
+/// The Rust definition of `\w`, spelled out with Unicode properties
+const UNICODE_WORD: &str = r"[\p{Alphabetic}\p{M}\p{Nd}\p{Pc}\p{Join_Control}]";
+
+/// Short names of the Unicode general categories, paired with their long names
+const GENERAL_CATEGORIES: &[(&str, &str)] = &[
+    ("L", "Letter"),
+    ("Lu", "Uppercase_Letter"),
+    ("Ll", "Lowercase_Letter"),
+    ("Lt", "Titlecase_Letter"),
+    ("LC", "Cased_Letter"),
+    ("Lm", "Modifier_Letter"),
+    ("Lo", "Other_Letter"),
+    ("M", "Mark"),
+    ("Mn", "Nonspacing_Mark"),
+    ("Mc", "Spacing_Mark"),
+    ("Me", "Enclosing_Mark"),
+    ("Z", "Separator"),
+    ("Zs", "Space_Separator"),
+    ("Zl", "Line_Separator"),
+    ("Zp", "Paragraph_Separator"),
+    ("S", "Symbol"),
+    ("Sm", "Math_Symbol"),
+    ("Sc", "Currency_Symbol"),
+    ("Sk", "Modifier_Symbol"),
+    ("So", "Other_Symbol"),
+    ("N", "Number"),
+    ("Nd", "Decimal_Number"),
+    ("Nl", "Letter_Number"),
+    ("No", "Other_Number"),
+    ("P", "Punctuation"),
+    ("Pd", "Dash_Punctuation"),
+    ("Ps", "Open_Punctuation"),
+    ("Pe", "Close_Punctuation"),
+    ("Pi", "Initial_Punctuation"),
+    ("Pf", "Final_Punctuation"),
+    ("Pc", "Connector_Punctuation"),
+    ("Po", "Other_Punctuation"),
+    ("C", "Other"),
+    ("Cc", "Control"),
+    ("Cf", "Format"),
+    ("Co", "Private_Use"),
+    ("Cs", "Surrogate"),
+    ("Cn", "Unassigned"),
+];
+
+/// Names of the Unicode scripts that can be written as properties
+const SCRIPTS: &[&str] = &[
+    "Arabic",
+    "Armenian",
+    "Bengali",
+    "Bopomofo",
+    "Braille",
+    "Buhid",
+    "Canadian_Aboriginal",
+    "Cherokee",
+    "Common",
+    "Cyrillic",
+    "Devanagari",
+    "Ethiopic",
+    "Georgian",
+    "Greek",
+    "Gujarati",
+    "Gurmukhi",
+    "Han",
+    "Hangul",
+    "Hanunoo",
+    "Hebrew",
+    "Hiragana",
+    "Inherited",
+    "Kannada",
+    "Katakana",
+    "Khmer",
+    "Lao",
+    "Latin",
+    "Limbu",
+    "Malayalam",
+    "Mongolian",
+    "Myanmar",
+    "Ogham",
+    "Oriya",
+    "Runic",
+    "Sinhala",
+    "Syriac",
+    "Tagalog",
+    "Tagbanwa",
+    "Tai_Le",
+    "Tamil",
+    "Telugu",
+    "Thaana",
+    "Thai",
+    "Tibetan",
+    "Yi",
+];
+
+/// Compare Unicode names the way Rust does, ignoring case, spaces, hyphens and underscores
+fn loosely(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Renders a parsed pattern in one dialect
+struct Exporter<'a> {
+    /// The target dialect
+    dialect: Dialect,
+    /// The original pattern, which spans in the parsed pattern refer to
+    pattern: &'a str,
+    /// Whether a PCRE pattern relies on Unicode classes or characters
+    needs_ucp: std::cell::Cell<bool>,
+}
+
+impl Exporter<'_> {
+    /// Note that the pattern relies on Unicode, which PCRE must be told about
+    fn uses_unicode(&self) {
+        if self.dialect == Dialect::Pcre {
+            self.needs_ucp.set(true);
+        }
+    }
+
+    /// Whether the dialect supports scoped flag groups such as `(?i:...)`
+    fn has_flag_groups(&self) -> bool {
+        matches!(self.dialect, Dialect::Pcre | Dialect::Python)
+    }
+
+    /// Open a non-capturing group, with flags if there are any
+    fn open_group(&self, flags: &str) -> &'static str {
+        match (self.dialect, flags.is_empty()) {
+            (Dialect::Posix, _) => "(",
+            (_, true) => "(?:",
+            (_, false) => "(?",
+        }
+    }
+
+    /// Render any expression. `atom` is set when the result must be a single unit, as for the
+    /// target of a repetition.
+    fn render(&self, node: &Ast, flags: Flags, atom: bool) -> Result<String, String> {
+        Ok(match node {
+            Ast::Empty(_) => String::new(),
+            Ast::Flags(set) => {
+                // A flag group on its own only affects what follows it, and there is nothing
+                return Flags::update(flags, &set.flags).map(|_| String::new());
+            }
+            Ast::Literal(literal) => {
+                if flags.case_insensitive && !self.has_flag_groups() {
+                    self.class_of(node, flags)?
+                } else {
+                    self.literal(literal.c)
+                }
+            }
+            Ast::Dot(_) => match (self.dialect, flags.dot_matches_new_line) {
+                (Dialect::EcmaScript, false) => r"[^\n]".to_string(),
+                (Dialect::EcmaScript, true) => r"[\s\S]".to_string(),
+                (Dialect::Posix, false) => "[^\n]".to_string(),
+                _ => ".".to_string(),
+            },
+            Ast::Assertion(assertion) => self.assertion(&assertion.kind, flags)?,
+            Ast::ClassPerl(class) => self.perl(&class.kind, class.negated, flags),
+            Ast::ClassUnicode(class) => self.unicode(node, class, flags)?,
+            Ast::ClassBracketed(class) => match (&class.kind, self.dialect) {
+                (ClassSet::Item(ClassSetItem::Ascii(ascii)), Dialect::Pcre | Dialect::Posix)
+                    if !flags.case_insensitive
+                        && (self.dialect == Dialect::Pcre
+                            || !matches!(
+                                ascii.kind,
+                                ClassAsciiKind::Ascii | ClassAsciiKind::Word
+                            )) =>
+                {
+                    let name = format!("{:?}", ascii.kind).to_lowercase();
+                    match (class.negated != ascii.negated, self.dialect) {
+                        (false, _) => format!("[[:{}:]]", name),
+                        (true, Dialect::Pcre) => format!("[[:^{}:]]", name),
+                        (true, _) => format!("[^[:{}:]]", name),
+                    }
+                }
+                _ => self.class_of(node, flags)?,
+            },
+            Ast::Repetition(repetition) => {
+                let greedy = repetition.greedy != flags.swap_greed;
+                if !greedy && self.dialect == Dialect::Posix {
+                    return Err("lazy repetition is not supported".to_string());
+                }
+                let operator = match &repetition.op.kind {
+                    RepetitionKind::ZeroOrOne => "?".to_string(),
+                    RepetitionKind::ZeroOrMore => "*".to_string(),
+                    RepetitionKind::OneOrMore => "+".to_string(),
+                    RepetitionKind::Range(range) => {
+                        let largest = match *range {
+                            RepetitionRange::Exactly(n) | RepetitionRange::AtLeast(n) => n,
+                            RepetitionRange::Bounded(_, m) => m,
+                        };
+                        if self.dialect == Dialect::Posix && largest > 255 {
+                            return Err(format!(
+                                "repetition counts above 255 are not supported, found {}",
+                                largest
+                            ));
+                        }
+                        match *range {
+                            RepetitionRange::Exactly(n) => format!("{{{}}}", n),
+                            RepetitionRange::AtLeast(n) => format!("{{{},}}", n),
+                            RepetitionRange::Bounded(n, m) => format!("{{{},{}}}", n, m),
+                        }
+                    }
+                };
+                format!(
+                    "{}{}{}",
+                    self.render(&repetition.ast, flags, true)?,
+                    operator,
+                    if greedy { "" } else { "?" }
+                )
+            }
+            Ast::Group(group) => match &group.kind {
+                GroupKind::CaptureIndex(_) => {
+                    format!("({})", self.render(&group.ast, flags, false)?)
+                }
+                GroupKind::CaptureName { name, .. } => {
+                    let name = &name.name;
+                    let mut chars = name.chars();
+                    if self.dialect != Dialect::Posix
+                        && !chars
+                            .next()
+                            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                        || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    {
+                        return Err(format!("the group name `{}` is not supported", name));
+                    }
+                    let inner = self.render(&group.ast, flags, false)?;
+                    match self.dialect {
+                        Dialect::EcmaScript | Dialect::Pcre => format!("(?<{}>{})", name, inner),
+                        Dialect::Python => format!("(?P<{}>{})", name, inner),
+                        Dialect::Posix => format!("({})", inner),
+                    }
+                }
+                GroupKind::NonCapturing(set) => {
+                    let inner_flags = flags.update(set)?;
+                    let keep = self.dialect != Dialect::Posix || needs_group(&group.ast, atom);
+                    let inner = self.render(&group.ast, inner_flags, atom && !keep)?;
+                    let changed = if self.has_flag_groups() {
+                        inner_flags.difference(&flags)
+                    } else {
+                        String::new()
+                    };
+                    if !keep {
+                        inner
+                    } else {
+                        let separator = if changed.is_empty() { "" } else { ":" };
+                        format!(
+                            "{}{}{}{})",
+                            self.open_group(&changed),
+                            changed,
+                            separator,
+                            inner
+                        )
+                    }
+                }
+            },
+            Ast::Alternation(alternation) => {
+                let options = alternation
+                    .asts
+                    .iter()
+                    .map(|option| self.render(option, flags, false))
+                    .collect::<Result<Vec<_>, _>>()?;
+                options.join("|")
+            }
+            Ast::Concat(concat) => self.concat(&concat.asts, flags)?,
+        })
+    }
+
+    /// Render the items of a concatenation, scoping any flags to the items that follow them
+    fn concat(&self, parts: &[Ast], flags: Flags) -> Result<String, String> {
+        let mut rendered = String::new();
+        let mut flags = flags;
+        for (index, part) in parts.iter().enumerate() {
+            if let Ast::Flags(set) = part {
+                // Flags apply to the rest of the expression, which becomes a scoped group
+                let outer = flags;
+                flags = flags.update(&set.flags)?;
+                let changed = flags.difference(&outer);
+                if self.has_flag_groups() && !changed.is_empty() {
+                    let rest = self.concat(&parts[index + 1..], flags)?;
+                    rendered.push_str(&format!("(?{}:{})", changed, rest));
+                    break;
+                }
+                continue;
+            }
+            rendered.push_str(&self.render(part, flags, false)?);
+        }
+        Ok(rendered)
+    }
+
+    /// Render a zero-width assertion
+    fn assertion(&self, kind: &AssertionKind, flags: Flags) -> Result<String, String> {
+        use AssertionKind::*;
+        let word_class = self.word_class(flags);
+        let (before, after) = (
+            format!("(?<={})", word_class),
+            format!("(?={})", word_class),
+        );
+        let (not_before, not_after) = (
+            format!("(?<!{})", word_class),
+            format!("(?!{})", word_class),
+        );
+        Ok(match (self.dialect, kind, flags.multi_line) {
+            (Dialect::Posix, StartLine | EndLine, true) => {
+                return Err("multi-line anchors are not supported".to_string())
+            }
+            (Dialect::Posix, StartLine | StartText, _) => "^".to_string(),
+            (Dialect::Posix, EndLine | EndText, _) => "$".to_string(),
+            (Dialect::Posix, _, _) => return Err("word boundaries are not supported".to_string()),
+            (Dialect::EcmaScript, StartLine, true) => r"(?<![^\n])".to_string(),
+            (Dialect::EcmaScript, EndLine, true) => r"(?![^\n])".to_string(),
+            (Dialect::EcmaScript, StartLine | StartText, _) => "^".to_string(),
+            (Dialect::EcmaScript, EndLine | EndText, _) => "$".to_string(),
+            (_, StartLine, _) => "^".to_string(),
+            (_, EndLine, true) => "$".to_string(),
+            (_, StartText, _) => r"\A".to_string(),
+            (Dialect::Pcre, EndLine | EndText, _) => r"\z".to_string(),
+            (_, EndLine | EndText, _) => r"\Z".to_string(),
+            (Dialect::Pcre | Dialect::Python, WordBoundary, _) if !flags.ascii => {
+                self.uses_unicode();
+                r"\b".to_string()
+            }
+            (Dialect::Pcre | Dialect::Python, NotWordBoundary, _) if !flags.ascii => {
+                self.uses_unicode();
+                r"\B".to_string()
+            }
+            (Dialect::EcmaScript, WordBoundary, _) if flags.ascii => r"\b".to_string(),
+            (Dialect::EcmaScript, NotWordBoundary, _) if flags.ascii => r"\B".to_string(),
+            (_, WordBoundary, _) => format!("(?:{}{}|{}{})", before, not_after, not_before, after),
+            (_, NotWordBoundary, _) => {
+                format!("(?:{}{}|{}{})", before, after, not_before, not_after)
+            }
+            (_, WordBoundaryStart | WordBoundaryStartAngle, _) => {
+                format!("{}{}", not_before, after)
+            }
+            (_, WordBoundaryEnd | WordBoundaryEndAngle, _) => format!("{}{}", before, not_after),
+            (_, WordBoundaryStartHalf, _) => not_before,
+            (_, WordBoundaryEndHalf, _) => not_after,
+        })
+    }
+
+    /// A class matching one word character, for use in lookarounds
+    fn word_class(&self, flags: Flags) -> String {
+        match (self.dialect, flags.ascii) {
+            (_, true) => "[0-9A-Za-z_]".to_string(),
+            (Dialect::EcmaScript, false) => UNICODE_WORD.to_string(),
+            _ => r"\w".to_string(),
+        }
+    }
+
+    /// Render a Perl class such as `\d`
+    fn perl(&self, kind: &ClassPerlKind, negated: bool, flags: Flags) -> String {
+        let caret = if negated { "^" } else { "" };
+        if flags.ascii {
+            return match (self.dialect, kind) {
+                (Dialect::Posix, ClassPerlKind::Space) => format!("[{}[:space:]]", caret),
+                (_, ClassPerlKind::Digit) => format!("[{}0-9]", caret),
+                (_, ClassPerlKind::Space) => format!(r"[{}\t\n\v\f\r ]", caret),
+                (_, ClassPerlKind::Word) => format!("[{}0-9A-Za-z_]", caret),
+            };
+        }
+        match self.dialect {
+            Dialect::EcmaScript => match (kind, negated) {
+                (ClassPerlKind::Digit, false) => r"\p{Nd}".to_string(),
+                (ClassPerlKind::Digit, true) => r"\P{Nd}".to_string(),
+                (ClassPerlKind::Space, false) => r"\p{White_Space}".to_string(),
+                (ClassPerlKind::Space, true) => r"\P{White_Space}".to_string(),
+                (ClassPerlKind::Word, _) => UNICODE_WORD.replacen('[', &format!("[{}", caret), 1),
+            },
+            Dialect::Posix => match kind {
+                ClassPerlKind::Digit => format!("[{}[:digit:]]", caret),
+                ClassPerlKind::Space => format!("[{}[:space:]]", caret),
+                ClassPerlKind::Word => format!("[{}[:alnum:]_]", caret),
+            },
+            Dialect::Pcre | Dialect::Python => {
+                self.uses_unicode();
+                let letter = match kind {
+                    ClassPerlKind::Digit => 'd',
+                    ClassPerlKind::Space => 's',
+                    ClassPerlKind::Word => 'w',
+                };
+                if negated {
+                    format!("\\{}", letter.to_ascii_uppercase())
+                } else {
+                    format!("\\{}", letter)
+                }
+            }
+        }
+    }
+
+    /// Render a Unicode class such as `\p{Greek}`, by name where the dialect knows it
+    fn unicode(
+        &self,
+        node: &Ast,
+        class: &ast::ClassUnicode,
+        flags: Flags,
+    ) -> Result<String, String> {
+        // Translate first, so that unknown names are reported
+        let expanded = self.class_of(node, flags)?;
+        if flags.case_insensitive || matches!(self.dialect, Dialect::Python | Dialect::Posix) {
+            return Ok(expanded);
+        }
+        let name = match &class.kind {
+            ClassUnicodeKind::OneLetter(letter) => letter.to_string(),
+            ClassUnicodeKind::Named(name) => name.clone(),
+            ClassUnicodeKind::NamedValue { .. } => return Ok(expanded),
+        };
+        self.uses_unicode();
+        let p = if class.negated { 'P' } else { 'p' };
+        if let Some((short, _)) = GENERAL_CATEGORIES.iter().find(|(short, long)| {
+            loosely(short) == loosely(&name) || loosely(long) == loosely(&name)
+        }) {
+            Ok(format!("\\{}{{{}}}", p, short))
+        } else if let Some(script) = SCRIPTS
+            .iter()
+            .find(|script| loosely(script) == loosely(&name))
+        {
+            Ok(match self.dialect {
+                Dialect::EcmaScript => format!("\\{}{{Script={}}}", p, script),
+                _ => format!("\\{}{{{}}}", p, script),
+            })
+        } else {
+            Ok(expanded)
+        }
+    }
+
+    /// Render a single-character expression as an explicit class, applying set operations and
+    /// case folding along the way
+    fn class_of(&self, node: &Ast, flags: Flags) -> Result<String, String> {
+        let translated = TranslatorBuilder::new()
+            .case_insensitive(flags.case_insensitive)
+            .unicode(!flags.ascii)
+            .utf8(false)
+            .build()
+            .translate(self.pattern, node)
+            .map_err(|e| e.to_string())?;
+        let ranges: Vec<(char, char)> = match translated.kind() {
+            HirKind::Class(Class::Unicode(class)) => class
+                .ranges()
+                .iter()
+                .map(|range| (range.start(), range.end()))
+                .collect(),
+            HirKind::Class(Class::Bytes(class)) => {
+                if class.ranges().iter().any(|range| range.end() > 0x7F) {
+                    return Err("classes of non-ASCII bytes are not supported".to_string());
+                }
+                class
+                    .ranges()
+                    .iter()
+                    .map(|range| (range.start() as char, range.end() as char))
+                    .collect()
+            }
+            HirKind::Literal(hir::Literal(bytes)) => {
+                let c = std::str::from_utf8(bytes)
+                    .ok()
+                    .and_then(|literal| literal.chars().next())
+                    .ok_or("literal bytes are not supported")?;
+                vec![(c, c)]
+            }
+            _ => unreachable!("single-character expressions translate to classes or literals"),
+        };
+        self.ranges(&ranges)
+    }
+
+    /// Render a set of character ranges as a bracketed class, negating it if that is shorter
+    fn ranges(&self, ranges: &[(char, char)]) -> Result<String, String> {
+        let mut complement = hir::ClassUnicode::new(
+            ranges
+                .iter()
+                .map(|&(s, e)| hir::ClassUnicodeRange::new(s, e)),
+        );
+        complement.negate();
+        let complement: Vec<(char, char)> = complement
+            .ranges()
+            .iter()
+            .map(|range| (range.start(), range.end()))
+            .collect();
+        let (negated, ranges) = if complement.len() < ranges.len() {
+            (true, complement.as_slice())
+        } else {
+            (false, ranges)
+        };
+        if ranges.is_empty() {
+            return match (self.dialect, negated) {
+                (Dialect::Posix, false) => Err("empty classes are not supported".to_string()),
+                (Dialect::Posix, true) => Ok(".".to_string()),
+                (Dialect::EcmaScript, true) => Ok(r"[\s\S]".to_string()),
+                (_, true) => Ok("(?s:.)".to_string()),
+                (_, false) => Ok("(?!)".to_string()),
+            };
+        }
+        let caret = if negated { "^" } else { "" };
+        if self.dialect != Dialect::Posix {
+            let body: String = ranges
+                .iter()
+                .map(|&(start, end)| match (end as u32) - (start as u32) {
+                    0 => self.class_char(start),
+                    1 => format!("{}{}", self.class_char(start), self.class_char(end)),
+                    _ => format!("{}-{}", self.class_char(start), self.class_char(end)),
+                })
+                .collect();
+            return Ok(format!("[{}{}]", caret, body));
+        }
+        // POSIX brackets have no escapes, so special characters go where they are taken literally
+        let mut specials = String::new();
+        let mut body = String::new();
+        for &(start, end) in ranges {
+            // Only the ends of a range matter, so peel special characters off of them
+            let special = |c: char| matches!(c, ']' | '-' | '^' | '[');
+            let step = |c: char, by: i32| char::from_u32((c as i32 + by) as u32).unwrap_or(c);
+            let (mut start, mut end) = (start, end);
+            while special(start) && start <= end {
+                specials.push(start);
+                if start == end {
+                    break;
+                }
+                start = step(start, 1);
+            }
+            if specials.ends_with(end) {
+                continue;
+            }
+            while special(end) {
+                specials.push(end);
+                end = step(end, -1);
+            }
+            match (end as u32) - (start as u32) {
+                0 => body.push(start),
+                1 => body.extend([start, end]),
+                _ => body.push_str(&format!("{}-{}", start, end)),
+            }
+        }
+        let has = |c: char| specials.contains(c);
+        if !negated && body.is_empty() && specials == "^" {
+            return Ok(r"\^".to_string());
+        }
+        Ok(format!(
+            "[{}{}{}{}{}{}]",
+            caret,
+            if has(']') { "]" } else { "" },
+            body,
+            if has('[') { "[" } else { "" },
+            if has('^') { "^" } else { "" },
+            if has('-') { "-" } else { "" },
+        ))
+    }
+
+    /// Escape a character outside of a class
+    fn literal(&self, c: char) -> String {
+        match self.dialect {
+            Dialect::Posix if ".[]()*+?{}|^$\\".contains(c) => format!("\\{}", c),
+            Dialect::Posix => c.to_string(),
+            Dialect::EcmaScript if r"^$\.*+?()[]{}|/".contains(c) => format!("\\{}", c),
+            Dialect::Pcre | Dialect::Python if c.is_ascii_punctuation() && c != '_' => {
+                format!("\\{}", c)
+            }
+            _ => self.code(c),
+        }
+    }
+
+    /// Escape a character inside a class, for dialects other than POSIX
+    fn class_char(&self, c: char) -> String {
+        match c {
+            '\\' | ']' | '[' | '^' | '-' => format!("\\{}", c),
+            _ if self.dialect != Dialect::EcmaScript && c.is_ascii_punctuation() && c != '_' => {
+                format!("\\{}", c)
+            }
+            _ => self.code(c),
+        }
+    }
+
+    /// Write a character as itself, or as an escape sequence if it is not visible
+    fn code(&self, c: char) -> String {
+        if !c.is_ascii() {
+            self.uses_unicode();
+        }
+        match c {
+            '\n' => r"\n".to_string(),
+            '\t' => r"\t".to_string(),
+            '\r' => r"\r".to_string(),
+            _ if c == ' ' || !(c.is_control() || c.is_whitespace()) => c.to_string(),
+            _ => match self.dialect {
+                Dialect::EcmaScript => format!("\\u{{{:X}}}", c as u32),
+                Dialect::Pcre => format!("\\x{{{:X}}}", c as u32),
+                Dialect::Python if (c as u32) <= 0xFFFF => format!("\\u{:04X}", c as u32),
+                Dialect::Python => format!("\\U{:08X}", c as u32),
+                Dialect::Posix => c.to_string(),
+            },
+        }
+    }
+}
+
+/// Whether a POSIX group is needed to keep its contents together
+fn needs_group(inner: &Ast, atom: bool) -> bool {
+    match inner {
+        Ast::Alternation(_) => true,
+        Ast::Concat(_) | Ast::Empty(_) | Ast::Flags(_) | Ast::Repetition(_) => atom,
+        _ => false,
+    }
+}
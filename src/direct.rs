@@ -15,7 +15,7 @@ where
     T: Into<String> + fmt::Display,
 {
     HumanRegex(
-        format!("(?:{})", escape(&*text.to_string())),
+        format!("(?:{})", escape(&text.to_string())),
         pd::<LiteralSymbolChain>,
     )
 }
@@ -46,5 +46,5 @@ where
 /// assert!(!regex_string.to_regex().is_match("007"));
 /// ```
 pub fn nonescaped_text(text: &str) -> HumanRegex<SymbolChain> {
-    HumanRegex(format!("(?:{})", text.to_string()), pd::<SymbolChain>)
+    HumanRegex(format!("(?:{})", text), pd::<SymbolChain>)
 }
@@ -0,0 +1,854 @@
+//! A textual form of the builder API, for patterns that live outside of Rust code
+//!
+//! The [parse] function reads the same expressions you would write in Rust, so patterns can be kept
+//! in configuration files and edited without learning regex syntax.
+//! ```
+//! use human_regex::dsl::parse;
+//! let regex_string = parse("beginning + exactly(4, digit) + text('-') + exactly(2, digit) + end").unwrap();
+//! assert!(regex_string.to_regex().is_match("2014-01"));
+//! assert!(!regex_string.to_regex().is_match("14-01"));
+//! ```
+//! ## Syntax
+//! - Builders are called by their function names. Parentheses are optional for builders that take
+//!   no arguments, so `digit` and `digit()` are the same thing.
+//! - `+` concatenates, `&` intersects two character classes and `!` negates, just like the
+//!   operators on [HumanRegex]. Quantifiers can be made lazy with `.lazy()`.
+//! - Text goes in single or double quotes, with backslash escapes (`\n`, `\t`, `\\`, `\'`, `\"`,
+//!   `\u{1F980}`). A one-character string is used wherever a character is expected.
+//! - Character ranges are written as `'a'..='z'` and lists as `['a', 'e']` or
+//!   `[text("cat"), text("dog")]`.
+//! - Unicode categories and scripts are given by variant name, as in `unicode_category(CurrencySymbol)`
+//!   or `unicode_script(UnicodeScript::Han)`.
+//!
+//! Mistakes are reported with the line and column where they occur:
+//! ```
+//! let error = human_regex::dsl::parse("digit +\n  exactly(300, digit)").unwrap_err();
+//! assert_eq!((error.line, error.column), (2, 11));
+//! assert_eq!(
+//!     error.to_string(),
+//!     "line 2, column 11: expected a count between 0 and 255, found `300`"
+//! );
+//! ```
+
+use super::humanregex::*;
+use super::*;
+use std::marker::PhantomData as pd;
+
+/// An error encountered while reading a pattern, along with where it happened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DslError {
+    /// The line of the error, starting from 1
+    pub line: usize,
+    /// The column of the error in characters, starting from 1
+    pub column: usize,
+    /// A description of what went wrong
+    pub message: String,
+}
+
+impl fmt::Display for DslError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for DslError {}
+
+/// Parse a builder expression into a [HumanRegex]
+/// ```
+/// use human_regex::dsl::parse;
+/// let regex_string = parse(r#"
+///     named_capture(one_or_more(within_range('a'..='z')), "user")
+///     + text("@")
+///     + named_capture(one_or_more(or([text("example"), text("test")])), "domain")
+/// "#).unwrap();
+/// let caps = regex_string.to_regex().captures("chris@example").unwrap();
+/// assert_eq!("chris", &caps["user"]);
+/// assert_eq!("example", &caps["domain"]);
+/// ```
+///
+/// The result is checked with the regex crate, so a pattern it would reject is an error here rather
+/// than a panic in [HumanRegex::to_regex]:
+/// ```
+/// use human_regex::dsl::parse;
+/// for source in [
+///     "within_range('z'..='a')",
+///     "between(5, 2, digit)",
+///     "named_capture(digit, 'a b')",
+///     "within_set(['^'])",
+///     "nonescaped_text('(')",
+/// ] {
+///     assert!(parse(source).unwrap_err().message.starts_with("not a valid regex: "));
+/// }
+/// assert_eq!(
+///     parse("text('a') + within_range('z'..='a')").unwrap_err().to_string(),
+///     "line 1, column 1: not a valid regex: invalid character class range, the start must be <= the end"
+/// );
+/// ```
+pub fn parse(source: &str) -> Result<HumanRegex, DslError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        position: 0,
+    };
+    let (value, at) = parser.expression()?;
+    let (token, trailing) = parser.next();
+    if token != Token::End {
+        return Err(trailing.error(format!("unexpected {}", token)));
+    }
+    match value {
+        Value::Regex(regex_string, _) => match regex::Regex::new(&regex_string) {
+            Ok(_) => Ok(HumanRegex(regex_string, pd::<SymbolChain>)),
+            Err(error) => Err(at.error(format!(
+                "not a valid regex: {}",
+                describe_invalid(&regex_string, error)
+            ))),
+        },
+        other => Err(at.error(format!("expected a pattern, found {}", other))),
+    }
+}
+
+/// A one-line description of why the regex crate rejected a regex string
+pub(crate) fn describe_invalid(regex_string: &str, error: regex::Error) -> String {
+    match regex_syntax::Parser::new().parse(regex_string) {
+        Err(regex_syntax::Error::Parse(error)) => error.kind().to_string(),
+        Err(regex_syntax::Error::Translate(error)) => error.kind().to_string(),
+        _ => error.to_string(),
+    }
+}
+
+/// The state of a [Value] that holds a regex, mirroring the type parameter of [HumanRegex]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    /// See [Standard]
+    Standard,
+    /// See [Custom]
+    Custom,
+    /// See [Ascii]
+    Ascii,
+    /// See [LiteralSymbolChain]
+    Literal,
+    /// See [SymbolChain]
+    Chain,
+    /// See [Quantifier]
+    Quantifier,
+}
+
+/// A dynamically typed value, either a regex or an argument to a builder
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    /// A regex string and its state
+    Regex(String, Kind),
+    /// A quoted string
+    Str(String),
+    /// A non-negative integer
+    Int(u64),
+    /// An inclusive range of characters
+    Range(char, char),
+    /// A list of values in square brackets
+    List(Vec<Value>),
+    /// A bare name that is not a builder, such as a Unicode category
+    Name(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Regex(_, Kind::Standard | Kind::Custom | Kind::Ascii) => {
+                write!(f, "a character class")
+            }
+            Value::Regex(_, Kind::Literal) => write!(f, "text"),
+            Value::Regex(_, Kind::Chain) => write!(f, "a pattern"),
+            Value::Regex(_, Kind::Quantifier) => write!(f, "a quantifier"),
+            Value::Str(string) => write!(f, "{:?}", string),
+            Value::Int(int) => write!(f, "`{}`", int),
+            Value::Range(start, end) => write!(f, "{:?}..={:?}", start, end),
+            Value::List(_) => write!(f, "a list"),
+            Value::Name(name) => write!(f, "unknown builder `{}`", name),
+        }
+    }
+}
+
+impl<T> From<(HumanRegex<T>, Kind)> for Value {
+    fn from((hr, kind): (HumanRegex<T>, Kind)) -> Self {
+        Value::Regex(hr.0, kind)
+    }
+}
+
+/// Names of all builders that take no arguments
+pub(crate) const NULLARY: &[&str] = &[
+    "any",
+    "digit",
+    "non_digit",
+    "word",
+    "non_word",
+    "whitespace",
+    "non_whitespace",
+    "word_boundary",
+    "non_word_boundary",
+    "beginning",
+    "end",
+    "beginning_of_text",
+    "end_of_text",
+    "alphanumeric",
+    "non_alphanumeric",
+    "alphabetic",
+    "non_alphabetic",
+    "lowercase",
+    "non_lowercase",
+    "uppercase",
+    "non_uppercase",
+    "hexdigit",
+    "non_hexdigit",
+    "ascii",
+    "non_ascii",
+    "blank",
+    "non_blank",
+    "control",
+    "non_control",
+    "graphical",
+    "non_graphical",
+    "printable",
+    "non_printable",
+    "punctuation",
+    "non_punctuation",
+];
+
+/// Call the builder with the given name on already evaluated arguments
+pub(crate) fn apply(name: &str, args: Vec<Value>) -> Result<Value, String> {
+    apply_tracked(name, args, &std::cell::Cell::new(0))
+}
+
+/// Like [apply], but counts the arguments taken so far so that an error can be traced to the
+/// argument that caused it
+fn apply_tracked(
+    name: &str,
+    args: Vec<Value>,
+    taken: &std::cell::Cell<usize>,
+) -> Result<Value, String> {
+    let arity = args.len();
+    let mut args = args.into_iter();
+    let mut arg = || {
+        taken.set(taken.get() + 1);
+        args.next()
+            .expect("arity is checked before arguments are taken")
+    };
+    Ok(match (name, arity) {
+        ("any", 0) => (any(), Kind::Standard).into(),
+        ("digit", 0) => (digit(), Kind::Standard).into(),
+        ("non_digit", 0) => (non_digit(), Kind::Standard).into(),
+        ("word", 0) => (word(), Kind::Standard).into(),
+        ("non_word", 0) => (non_word(), Kind::Standard).into(),
+        ("whitespace", 0) => (whitespace(), Kind::Standard).into(),
+        ("non_whitespace", 0) => (non_whitespace(), Kind::Standard).into(),
+        ("word_boundary", 0) => (word_boundary(), Kind::Standard).into(),
+        ("non_word_boundary", 0) => (non_word_boundary(), Kind::Standard).into(),
+        ("beginning", 0) => (beginning(), Kind::Standard).into(),
+        ("end", 0) => (end(), Kind::Standard).into(),
+        ("beginning_of_text", 0) => (beginning_of_text(), Kind::Standard).into(),
+        ("end_of_text", 0) => (end_of_text(), Kind::Standard).into(),
+        ("alphanumeric", 0) => (alphanumeric(), Kind::Ascii).into(),
+        ("non_alphanumeric", 0) => (non_alphanumeric(), Kind::Ascii).into(),
+        ("alphabetic", 0) => (alphabetic(), Kind::Ascii).into(),
+        ("non_alphabetic", 0) => (non_alphabetic(), Kind::Ascii).into(),
+        ("lowercase", 0) => (lowercase(), Kind::Ascii).into(),
+        ("non_lowercase", 0) => (non_lowercase(), Kind::Ascii).into(),
+        ("uppercase", 0) => (uppercase(), Kind::Ascii).into(),
+        ("non_uppercase", 0) => (non_uppercase(), Kind::Ascii).into(),
+        ("hexdigit", 0) => (hexdigit(), Kind::Ascii).into(),
+        ("non_hexdigit", 0) => (non_hexdigit(), Kind::Ascii).into(),
+        ("ascii", 0) => (ascii(), Kind::Ascii).into(),
+        ("non_ascii", 0) => (non_ascii(), Kind::Ascii).into(),
+        ("blank", 0) => (blank(), Kind::Ascii).into(),
+        ("non_blank", 0) => (non_blank(), Kind::Ascii).into(),
+        ("control", 0) => (control(), Kind::Ascii).into(),
+        ("non_control", 0) => (non_control(), Kind::Ascii).into(),
+        ("graphical", 0) => (graphical(), Kind::Ascii).into(),
+        ("non_graphical", 0) => (non_graphical(), Kind::Ascii).into(),
+        ("printable", 0) => (printable(), Kind::Ascii).into(),
+        ("non_printable", 0) => (non_printable(), Kind::Ascii).into(),
+        ("punctuation", 0) => (punctuation(), Kind::Ascii).into(),
+        ("non_punctuation", 0) => (non_punctuation(), Kind::Ascii).into(),
+        ("within_range", 1) => (within_range(range(arg())?), Kind::Custom).into(),
+        ("without_range", 1) => (without_range(range(arg())?), Kind::Custom).into(),
+        ("within_set", 1) => (within_set(&set(arg())?), Kind::Custom).into(),
+        ("without_set", 1) => (without_set(&set(arg())?), Kind::Custom).into(),
+        ("unicode_category", 1) => (unicode_category(parse_name(arg())?), Kind::Standard).into(),
+        ("non_unicode_category", 1) => {
+            (non_unicode_category(parse_name(arg())?), Kind::Standard).into()
+        }
+        ("unicode_script", 1) => (unicode_script(parse_name(arg())?), Kind::Standard).into(),
+        ("non_unicode_script", 1) => {
+            (non_unicode_script(parse_name(arg())?), Kind::Standard).into()
+        }
+        ("at_least", 2) => (at_least(count(arg())?, chain(arg())?), Kind::Quantifier).into(),
+        ("between", 3) => (
+            between(count(arg())?, count(arg())?, chain(arg())?),
+            Kind::Quantifier,
+        )
+            .into(),
+        ("one_or_more", 1) => (one_or_more(chain(arg())?), Kind::Quantifier).into(),
+        ("zero_or_more", 1) => (zero_or_more(chain(arg())?), Kind::Quantifier).into(),
+        ("zero_or_one", 1) => (zero_or_one(chain(arg())?), Kind::Quantifier).into(),
+        ("exactly", 2) => (exactly(count(arg())?, chain(arg())?), Kind::Quantifier).into(),
+        ("lazy", 1) => match arg() {
+            Value::Regex(regex_string, Kind::Quantifier) => (
+                HumanRegex(regex_string, pd::<Quantifier>).lazy(),
+                Kind::Chain,
+            )
+                .into(),
+            other => return Err(format!("only quantifiers can be lazy, found {}", other)),
+        },
+        ("or", n) if n > 0 => {
+            let options = match arg() {
+                Value::List(options) if n == 1 => options,
+                first => std::iter::once(first).chain(args).collect(),
+            };
+            if options.is_empty() {
+                return Err("`or` needs at least one option".to_string());
+            }
+            let options = options
+                .into_iter()
+                .map(chain)
+                .collect::<Result<Vec<_>, _>>()?;
+            (or(&options), Kind::Chain).into()
+        }
+        ("and", 2) => (and(class(arg())?, class(arg())?), Kind::Custom).into(),
+        ("xor", 2) => (xor(class(arg())?, class(arg())?), Kind::Custom).into(),
+        ("subtract", 2) => (subtract(class(arg())?, class(arg())?), Kind::Custom).into(),
+        ("not", 1) => negate(arg())?,
+        ("text", 1) => (text(string(arg())?), Kind::Literal).into(),
+        ("nonescaped_text", 1) => (nonescaped_text(&string(arg())?), Kind::Chain).into(),
+        ("capture", 1) => (capture(chain(arg())?), Kind::Chain).into(),
+        ("named_capture", 2) => {
+            let target = chain(arg())?;
+            let name = match arg() {
+                Value::Str(name) | Value::Name(name) => name,
+                other => return Err(format!("expected a capture name, found {}", other)),
+            };
+            (named_capture(target, &name), Kind::Chain).into()
+        }
+        ("case_insensitive", 1) => (case_insensitive(chain(arg())?), Kind::Chain).into(),
+        ("multi_line_mode", 1) => (multi_line_mode(chain(arg())?), Kind::Chain).into(),
+        ("dot_matches_newline_too", 1) => {
+            (dot_matches_newline_too(chain(arg())?), Kind::Chain).into()
+        }
+        ("disable_unicode", 1) => (disable_unicode(chain(arg())?), Kind::Chain).into(),
+        ("concat", n) => {
+            let mut regex_string = String::new();
+            for _ in 0..n {
+                regex_string.push_str(&chain(arg())?.0);
+            }
+            Value::Regex(regex_string, Kind::Chain)
+        }
+        _ if is_builder(name) => {
+            return Err(format!(
+                "`{}` does not take {} argument{}",
+                name,
+                arity,
+                if arity == 1 { "" } else { "s" }
+            ))
+        }
+        _ => return Err(format!("unknown builder `{}`", name)),
+    })
+}
+
+/// Whether a name refers to any builder, regardless of arguments
+fn is_builder(name: &str) -> bool {
+    NULLARY.contains(&name)
+        || [
+            "within_range",
+            "without_range",
+            "within_set",
+            "without_set",
+            "unicode_category",
+            "non_unicode_category",
+            "unicode_script",
+            "non_unicode_script",
+            "at_least",
+            "between",
+            "one_or_more",
+            "zero_or_more",
+            "zero_or_one",
+            "exactly",
+            "lazy",
+            "or",
+            "and",
+            "xor",
+            "subtract",
+            "not",
+            "text",
+            "nonescaped_text",
+            "capture",
+            "named_capture",
+            "case_insensitive",
+            "multi_line_mode",
+            "dot_matches_newline_too",
+            "disable_unicode",
+            "concat",
+        ]
+        .contains(&name)
+}
+
+/// Negate a character class or literal text, following the `!` operator on [HumanRegex]
+fn negate(value: Value) -> Result<Value, String> {
+    Ok(match value {
+        Value::Regex(regex_string, Kind::Standard) => (
+            !HumanRegex(regex_string, pd::<SymbolClass<Standard>>),
+            Kind::Standard,
+        )
+            .into(),
+        Value::Regex(regex_string, Kind::Custom) => (
+            !HumanRegex(regex_string, pd::<SymbolClass<Custom>>),
+            Kind::Custom,
+        )
+            .into(),
+        Value::Regex(regex_string, Kind::Ascii) => (
+            !HumanRegex(regex_string, pd::<SymbolClass<Ascii>>),
+            Kind::Ascii,
+        )
+            .into(),
+        Value::Regex(regex_string, Kind::Literal) => (
+            !HumanRegex(regex_string, pd::<LiteralSymbolChain>),
+            Kind::Chain,
+        )
+            .into(),
+        other => {
+            return Err(format!(
+                "only character classes and text can be negated, found {}",
+                other
+            ))
+        }
+    })
+}
+
+/// Take any regex as a [SymbolChain]
+fn chain(value: Value) -> Result<HumanRegex<SymbolChain>, String> {
+    match value {
+        Value::Regex(regex_string, _) => Ok(HumanRegex(regex_string, pd::<SymbolChain>)),
+        other => Err(format!("expected a pattern, found {}", other)),
+    }
+}
+
+/// Take a regex that must be a single-character class
+fn class(value: Value) -> Result<HumanRegex<SymbolClass<Custom>>, String> {
+    match value {
+        Value::Regex(regex_string, Kind::Standard | Kind::Custom | Kind::Ascii) => {
+            Ok(HumanRegex(regex_string, pd::<SymbolClass<Custom>>))
+        }
+        other => Err(format!("expected a character class, found {}", other)),
+    }
+}
+
+/// Take a repetition count
+fn count(value: Value) -> Result<u8, String> {
+    match value {
+        Value::Int(n) if n <= u8::MAX as u64 => Ok(n as u8),
+        other => Err(format!(
+            "expected a count between 0 and 255, found {}",
+            other
+        )),
+    }
+}
+
+/// Take a string
+fn string(value: Value) -> Result<String, String> {
+    match value {
+        Value::Str(string) => Ok(string),
+        other => Err(format!("expected a string, found {}", other)),
+    }
+}
+
+/// Take a string that holds exactly one character
+fn character(value: Value) -> Result<char, String> {
+    match value {
+        Value::Str(ref string) if string.chars().count() == 1 => {
+            Ok(string.chars().next().expect("length checked above"))
+        }
+        other => Err(format!("expected a single character, found {}", other)),
+    }
+}
+
+/// Take a character range
+fn range(value: Value) -> Result<std::ops::RangeInclusive<char>, String> {
+    match value {
+        Value::Range(start, end) => Ok(start..=end),
+        other => Err(format!("expected a character range, found {}", other)),
+    }
+}
+
+/// Take a set of characters, either as a list or as the characters of a single string
+fn set(value: Value) -> Result<Vec<String>, String> {
+    match value {
+        Value::List(items) => items
+            .into_iter()
+            .map(|item| character(item).map(String::from))
+            .collect(),
+        Value::Str(string) => Ok(string.chars().map(String::from).collect()),
+        other => Err(format!("expected a list of characters, found {}", other)),
+    }
+}
+
+/// Take the name of a Unicode category or script, optionally qualified with its enum
+fn parse_name<T: std::str::FromStr<Err = String>>(value: Value) -> Result<T, String> {
+    match value {
+        Value::Name(name) | Value::Str(name) => {
+            name.rsplit("::").next().unwrap_or_default().parse::<T>()
+        }
+        other => Err(format!("expected a Unicode name, found {}", other)),
+    }
+}
+
+/// Apply an operator, pointing any error at the operand that caused it
+fn binary(
+    name: &str,
+    (lhs, lhs_at): (Value, Position),
+    (rhs, rhs_at): (Value, Position),
+    at: Position,
+) -> Result<Value, DslError> {
+    let accepts = |value: &Value| match value {
+        Value::Regex(_, Kind::Standard | Kind::Custom | Kind::Ascii) => true,
+        Value::Regex(_, _) => name != "and",
+        _ => false,
+    };
+    let blame = if !accepts(&lhs) {
+        lhs_at
+    } else if !accepts(&rhs) {
+        rhs_at
+    } else {
+        at
+    };
+    apply(name, vec![lhs, rhs]).map_err(|message| blame.error(message))
+}
+
+/// A location in the source text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    /// Line, starting from 1
+    line: usize,
+    /// Column in characters, starting from 1
+    column: usize,
+}
+
+impl Position {
+    /// Create an error located here
+    fn error(self, message: String) -> DslError {
+        DslError {
+            line: self.line,
+            column: self.column,
+            message,
+        }
+    }
+}
+
+/// The smallest meaningful pieces of source text
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A builder or other name, possibly with `::` path segments
+    Ident(String),
+    /// A quoted string
+    Str(String),
+    /// An integer
+    Int(u64),
+    /// An operator or delimiter
+    Punct(&'static str),
+    /// The end of the source text
+    End,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "`{}`", name),
+            Token::Str(string) => write!(f, "{:?}", string),
+            Token::Int(int) => write!(f, "`{}`", int),
+            Token::Punct(punct) => write!(f, "`{}`", punct),
+            Token::End => write!(f, "end of input"),
+        }
+    }
+}
+
+/// All operators and delimiters, longest first so that `..=` is not read as `.`
+const PUNCTUATION: &[&str] = &["..=", "+", "&", "!", ".", "(", ")", "[", "]", ","];
+
+/// Split source text into tokens, each paired with where it starts
+fn tokenize(source: &str) -> Result<Vec<(Token, Position)>, DslError> {
+    let mut tokens = vec![];
+    let mut chars = source.chars().peekable();
+    let mut here = Position { line: 1, column: 1 };
+    let advance = |chars: &mut std::iter::Peekable<std::str::Chars>, here: &mut Position| {
+        let next = chars.next();
+        if next == Some('\n') {
+            here.line += 1;
+            here.column = 1;
+        } else if next.is_some() {
+            here.column += 1;
+        }
+        next
+    };
+    while let Some(&next) = chars.peek() {
+        let start = here;
+        let token = match next {
+            _ if next.is_whitespace() => {
+                advance(&mut chars, &mut here);
+                continue;
+            }
+            'a'..='z' | 'A'..='Z' | '_' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                        name.push(c);
+                        advance(&mut chars, &mut here);
+                    } else {
+                        break;
+                    }
+                }
+                Token::Ident(name)
+            }
+            '0'..='9' => {
+                let mut digits = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_digit() {
+                        digits.push(c);
+                        advance(&mut chars, &mut here);
+                    } else {
+                        break;
+                    }
+                }
+                Token::Int(
+                    digits.parse().map_err(|_| {
+                        start.error(format!("the number `{}` is too large", digits))
+                    })?,
+                )
+            }
+            '\'' | '"' => {
+                advance(&mut chars, &mut here);
+                let mut string = String::new();
+                loop {
+                    let escape_start = here;
+                    match advance(&mut chars, &mut here) {
+                        None => return Err(start.error("unterminated string".to_string())),
+                        Some(c) if c == next => break,
+                        Some('\\') => match advance(&mut chars, &mut here) {
+                            Some('n') => string.push('\n'),
+                            Some('t') => string.push('\t'),
+                            Some('r') => string.push('\r'),
+                            Some('0') => string.push('\0'),
+                            Some(c @ ('\\' | '\'' | '"')) => string.push(c),
+                            Some('u') if chars.peek() == Some(&'{') => {
+                                advance(&mut chars, &mut here);
+                                let mut hex = String::new();
+                                while let Some(c) = advance(&mut chars, &mut here) {
+                                    if c == '}' {
+                                        break;
+                                    }
+                                    hex.push(c);
+                                }
+                                string.push(
+                                    u32::from_str_radix(&hex, 16)
+                                        .ok()
+                                        .and_then(char::from_u32)
+                                        .ok_or_else(|| {
+                                            escape_start
+                                                .error(format!("invalid unicode escape `{}`", hex))
+                                        })?,
+                                )
+                            }
+                            other => {
+                                return Err(escape_start.error(format!(
+                                    "unknown escape `\\{}`",
+                                    other.map(String::from).unwrap_or_default()
+                                )))
+                            }
+                        },
+                        Some(c) => string.push(c),
+                    }
+                }
+                Token::Str(string)
+            }
+            _ => {
+                let rest: String = chars.clone().take(3).collect();
+                let punct = PUNCTUATION
+                    .iter()
+                    .copied()
+                    .find(|punct| rest.starts_with(punct))
+                    .ok_or_else(|| start.error(format!("unexpected character `{}`", next)))?;
+                for _ in 0..punct.len() {
+                    advance(&mut chars, &mut here);
+                }
+                Token::Punct(punct)
+            }
+        };
+        tokens.push((token, start));
+    }
+    tokens.push((Token::End, here));
+    Ok(tokens)
+}
+
+/// A recursive descent parser that evaluates builder calls as it reads them
+struct Parser {
+    /// All tokens of the source, ending with [Token::End]
+    tokens: Vec<(Token, Position)>,
+    /// Index of the next token to read
+    position: usize,
+}
+
+impl Parser {
+    /// Look at the next token without consuming it
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    /// Consume the next token
+    fn next(&mut self) -> (Token, Position) {
+        let next = self.tokens[self.position].clone();
+        if next.0 != Token::End {
+            self.position += 1;
+        }
+        next
+    }
+
+    /// Consume the next token if it is the given punctuation
+    fn eat(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Token::Punct(next) if *next == punct) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume the given punctuation or fail
+    fn expect(&mut self, punct: &str) -> Result<(), DslError> {
+        let (token, at) = self.next();
+        if matches!(token, Token::Punct(next) if next == punct) {
+            Ok(())
+        } else {
+            Err(at.error(format!("expected `{}`, found {}", punct, token)))
+        }
+    }
+
+    /// `expression := sum ('&' sum)*`, as `&` binds more loosely than `+` in Rust
+    fn expression(&mut self) -> Result<(Value, Position), DslError> {
+        let (mut lhs, start) = self.sum()?;
+        while let (Token::Punct("&"), at) = self.tokens[self.position].clone() {
+            self.position += 1;
+            let rhs = self.sum()?;
+            lhs = binary("and", (lhs, start), rhs, at)?;
+        }
+        Ok((lhs, start))
+    }
+
+    /// `sum := unary ('+' unary)*`
+    fn sum(&mut self) -> Result<(Value, Position), DslError> {
+        let (mut lhs, start) = self.unary()?;
+        while let (Token::Punct("+"), at) = self.tokens[self.position].clone() {
+            self.position += 1;
+            let rhs = self.unary()?;
+            lhs = binary("concat", (lhs, start), rhs, at)?;
+        }
+        Ok((lhs, start))
+    }
+
+    /// `unary := '!' unary | postfix`
+    fn unary(&mut self) -> Result<(Value, Position), DslError> {
+        if let (Token::Punct("!"), at) = self.tokens[self.position].clone() {
+            self.position += 1;
+            let (operand, operand_at) = self.unary()?;
+            let negated =
+                apply("not", vec![operand]).map_err(|message| operand_at.error(message))?;
+            return Ok((negated, at));
+        }
+        self.postfix()
+    }
+
+    /// `postfix := primary ('.' 'lazy' '(' ')')*`
+    fn postfix(&mut self) -> Result<(Value, Position), DslError> {
+        let (mut value, start) = self.primary()?;
+        while self.eat(".") {
+            let (method, at) = self.next();
+            if method != Token::Ident("lazy".to_string()) {
+                return Err(at.error(format!("expected method `lazy`, found {}", method)));
+            }
+            self.expect("(")?;
+            self.expect(")")?;
+            value = apply("lazy", vec![value]).map_err(|message| at.error(message))?;
+        }
+        Ok((value, start))
+    }
+
+    /// `primary := call | string ['..=' string] | int | list | '(' expression ')'`
+    fn primary(&mut self) -> Result<(Value, Position), DslError> {
+        let (token, at) = self.next();
+        let value = match token {
+            Token::Ident(name) => {
+                if self.eat("(") {
+                    let (args, positions): (Vec<_>, Vec<_>) =
+                        self.arguments(")")?.into_iter().unzip();
+                    let taken = std::cell::Cell::new(0);
+                    apply_tracked(&name, args, &taken).map_err(|message| {
+                        match taken.get() {
+                            0 => at,
+                            n => positions[n - 1],
+                        }
+                        .error(message)
+                    })?
+                } else if NULLARY.contains(&name.as_str()) {
+                    apply(&name, vec![]).map_err(|message| at.error(message))?
+                } else {
+                    Value::Name(name)
+                }
+            }
+            Token::Str(string) => {
+                if self.eat("..=") {
+                    let (end, end_at) = self.next();
+                    let start =
+                        character(Value::Str(string)).map_err(|message| at.error(message))?;
+                    let end = match end {
+                        Token::Str(end) => {
+                            character(Value::Str(end)).map_err(|message| end_at.error(message))?
+                        }
+                        other => {
+                            return Err(end_at.error(format!(
+                                "expected the end of a character range, found {}",
+                                other
+                            )))
+                        }
+                    };
+                    Value::Range(start, end)
+                } else {
+                    Value::Str(string)
+                }
+            }
+            Token::Int(int) => Value::Int(int),
+            Token::Punct("[") => Value::List(
+                self.arguments("]")?
+                    .into_iter()
+                    .map(|(value, _)| value)
+                    .collect(),
+            ),
+            Token::Punct("(") => {
+                let (value, _) = self.expression()?;
+                self.expect(")")?;
+                value
+            }
+            other => return Err(at.error(format!("unexpected {}", other))),
+        };
+        Ok((value, at))
+    }
+
+    /// Comma separated expressions up to the closing delimiter, allowing a trailing comma
+    fn arguments(&mut self, close: &str) -> Result<Vec<(Value, Position)>, DslError> {
+        let mut args = vec![];
+        while !self.eat(close) {
+            args.push(self.expression()?);
+            if !self.eat(",") {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(args)
+    }
+}
@@ -0,0 +1,258 @@
+//! Functions for building a large pattern out of named rules that refer to each other
+//!
+//! A [Grammar] holds the rules, and [Grammar::expand] replaces every [rule_ref] with the rule it
+//! names to make one regex string. A rule cannot refer to itself, directly or through other rules,
+//! since the repetition that recursion allows cannot be matched by a regex.
+//! ```
+//! use human_regex::{beginning, end, number_in_range, rule_ref, text, Grammar};
+//! let grammar = Grammar::new()
+//!     .rule("octet", number_in_range(0..=255))
+//!     .rule("dot", text("."))
+//!     .rule(
+//!         "ipv4",
+//!         rule_ref("octet") + rule_ref("dot") + rule_ref("octet") + rule_ref("dot")
+//!             + rule_ref("octet") + rule_ref("dot") + rule_ref("octet"),
+//!     );
+//! let regex_string = beginning() + grammar.expand("ipv4").unwrap() + end();
+//! assert!(regex_string.to_regex().is_match("192.168.0.255"));
+//! assert!(!regex_string.to_regex().is_match("192.168.0.256"));
+//! ```
+
+use super::dsl::describe_invalid;
+use super::humanregex::*;
+use regex_syntax::ast::{self, Ast, GroupKind};
+use std::collections::HashMap;
+use std::marker::PhantomData as pd;
+
+/// The start of every reference: a group that never matches and that holds the name of the rule.
+/// References are recognized by this text, so a regex string written by hand that starts a group
+/// the same way is taken for a reference too.
+const REFERENCE: &str = r"(?:[^\s\S]rule_ref(?:";
+
+/// Refers to the rule with the given name, which [Grammar::expand] replaces with the rule. Outside a
+/// grammar, a reference never matches.
+///
+/// A reference is a group that never matches, written as `(?:[^\s\S]rule_ref(?:name))`, and is
+/// recognized by that text. A group written the same way by hand, such as with
+/// [nonescaped_text](crate::nonescaped_text), is taken for a reference too.
+/// ```
+/// use human_regex::{rule_ref, text};
+/// assert!(!(text("a") + rule_ref("b")).to_regex().is_match("ab"));
+/// ```
+pub fn rule_ref(name: &str) -> HumanRegex<SymbolChain> {
+    HumanRegex(
+        format!("{}{}))", REFERENCE, regex::escape(name)),
+        pd::<SymbolChain>,
+    )
+}
+
+/// An error for a [Grammar] that cannot be expanded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrammarError {
+    /// A description of what went wrong
+    pub message: String,
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid grammar: {}", self.message)
+    }
+}
+
+impl std::error::Error for GrammarError {}
+
+/// A set of named rules, each a regex that may refer to other rules with [rule_ref]
+#[derive(Debug, Clone, Default)]
+pub struct Grammar {
+    /// The name and regex string of every rule, in the order they were added
+    rules: Vec<(String, String)>,
+}
+
+impl Grammar {
+    /// A grammar without any rules
+    pub fn new() -> Self {
+        Grammar { rules: Vec::new() }
+    }
+
+    /// Add a rule with the given name
+    pub fn rule<T>(mut self, name: &str, pattern: HumanRegex<T>) -> Self {
+        self.rules.push((name.to_string(), pattern.0));
+        self
+    }
+
+    /// Make one regex string out of the rule with the given name by replacing every reference in it,
+    /// and in the rules it refers to, with the rule it names
+    /// ```
+    /// use human_regex::{digit, one_or_more, rule_ref, text, Grammar};
+    /// let grammar = Grammar::new()
+    ///     .rule("list", rule_ref("item") + text(",") + rule_ref("items"))
+    ///     .rule("items", rule_ref("list"))
+    ///     .rule("item", one_or_more(digit()));
+    /// assert_eq!(
+    ///     grammar.expand("list").unwrap_err().to_string(),
+    ///     "invalid grammar: rule `list` refers to itself through list -> items -> list"
+    /// );
+    ///
+    /// let grammar = grammar.rule("pair", rule_ref("item") + text(":") + rule_ref("value"));
+    /// assert_eq!(
+    ///     grammar.expand("pair").unwrap_err().to_string(),
+    ///     "invalid grammar: rule `pair` refers to `value`, which is not defined"
+    /// );
+    /// ```
+    ///
+    /// Each reference is replaced with a copy of the rule, so a rule with a
+    /// [named_capture](crate::named_capture) in it can only be referred to once:
+    /// ```
+    /// use human_regex::{digit, named_capture, rule_ref, text, within_range, Grammar};
+    /// let grammar = Grammar::new()
+    ///     .rule("digit", named_capture(digit(), "digit"))
+    ///     .rule("pair", rule_ref("digit") + text(",") + rule_ref("digit"))
+    ///     .rule("backwards", within_range('z'..='a'));
+    /// assert_eq!(
+    ///     grammar.expand("pair").unwrap_err().to_string(),
+    ///     "invalid grammar: the expansion of rule `pair` is not a valid regex: duplicate capture group name"
+    /// );
+    /// assert_eq!(
+    ///     grammar.expand("backwards").unwrap_err().to_string(),
+    ///     "invalid grammar: rule `backwards` is not a valid regex: invalid character class range, the start must be <= the end"
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a rule is not defined, is defined more than once or is not a valid
+    /// regex, if a rule refers to itself, or if the expansion is not a valid regex.
+    pub fn expand(&self, name: &str) -> Result<HumanRegex<SymbolChain>, GrammarError> {
+        if let Some((duplicate, _)) = self
+            .rules
+            .iter()
+            .enumerate()
+            .find(|(index, (rule, _))| self.rules[..*index].iter().any(|(other, _)| other == rule))
+            .map(|(_, rule)| rule)
+        {
+            return Err(GrammarError {
+                message: format!("rule `{}` is defined more than once", duplicate),
+            });
+        }
+        if !self.rules.iter().any(|(rule, _)| rule == name) {
+            return Err(GrammarError {
+                message: format!("rule `{}` is not defined", name),
+            });
+        }
+        let expanded = self.expand_rule(name, &mut Vec::new(), &mut HashMap::new())?;
+        if let Err(error) = regex::Regex::new(&expanded) {
+            return Err(GrammarError {
+                message: format!(
+                    "the expansion of rule `{}` is not a valid regex: {}",
+                    name,
+                    describe_invalid(&expanded, error)
+                ),
+            });
+        }
+        Ok(HumanRegex(expanded, pd::<SymbolChain>))
+    }
+
+    /// Expand a rule that is known to exist, given the rules being expanded around it and the rules
+    /// expanded already
+    fn expand_rule<'g>(
+        &'g self,
+        name: &str,
+        path: &mut Vec<&'g str>,
+        expanded: &mut HashMap<&'g str, String>,
+    ) -> Result<String, GrammarError> {
+        let (name, pattern) = self.rules.iter().find(|(rule, _)| rule == name).unwrap();
+        if let Some(done) = expanded.get(name.as_str()) {
+            return Ok(done.clone());
+        }
+        if let Some(position) = path.iter().position(|rule| rule == name) {
+            let cycle: Vec<&str> = path[position..]
+                .iter()
+                .copied()
+                .chain([name.as_str()])
+                .collect();
+            return Err(GrammarError {
+                message: format!(
+                    "rule `{}` refers to itself through {}",
+                    name,
+                    cycle.join(" -> ")
+                ),
+            });
+        }
+        path.push(name);
+        let mut result = String::with_capacity(pattern.len());
+        let mut copied = 0;
+        let found = references(pattern).map_err(|error| GrammarError {
+            message: format!("rule `{}` is not a valid regex: {}", name, error),
+        })?;
+        for (start, end, reference) in found {
+            if !self.rules.iter().any(|(rule, _)| *rule == reference) {
+                return Err(GrammarError {
+                    message: format!(
+                        "rule `{}` refers to `{}`, which is not defined",
+                        name, reference
+                    ),
+                });
+            }
+            result.push_str(&pattern[copied..start]);
+            result.push_str(&format!(
+                "(?:{})",
+                self.expand_rule(&reference, path, expanded)?
+            ));
+            copied = end;
+        }
+        result.push_str(&pattern[copied..]);
+        path.pop();
+        expanded.insert(name, result.clone());
+        Ok(result)
+    }
+}
+
+/// Find the start, end and rule name of every reference in a regex string, in the order they appear,
+/// or why the regex string does not parse
+fn references(pattern: &str) -> Result<Vec<(usize, usize, String)>, ast::ErrorKind> {
+    /// Find the references in a parsed regex
+    fn collect_references(node: &Ast, pattern: &str, found: &mut Vec<(usize, usize, String)>) {
+        match node {
+            Ast::Group(group) => {
+                let text = &pattern[group.span.start.offset..group.span.end.offset];
+                match (&group.kind, text.strip_prefix(REFERENCE)) {
+                    (GroupKind::NonCapturing(_), Some(rest)) => found.push((
+                        group.span.start.offset,
+                        group.span.end.offset,
+                        unescape(rest.strip_suffix("))").unwrap()),
+                    )),
+                    _ => collect_references(&group.ast, pattern, found),
+                }
+            }
+            Ast::Repetition(repetition) => collect_references(&repetition.ast, pattern, found),
+            Ast::Alternation(alternation) => alternation
+                .asts
+                .iter()
+                .for_each(|option| collect_references(option, pattern, found)),
+            Ast::Concat(concat) => concat
+                .asts
+                .iter()
+                .for_each(|part| collect_references(part, pattern, found)),
+            _ => {}
+        }
+    }
+    let parsed = ast::parse::Parser::new()
+        .parse(pattern)
+        .map_err(|error| error.kind().clone())?;
+    let mut found = Vec::new();
+    collect_references(&parsed, pattern, &mut found);
+    Ok(found)
+}
+
+/// Undo [regex::escape] on a rule name
+fn unescape(escaped: &str) -> String {
+    let mut name = String::with_capacity(escaped.len());
+    let mut characters = escaped.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            '\\' => name.extend(characters.next()),
+            _ => name.push(character),
+        }
+    }
+    name
+}
@@ -1,3 +1,5 @@
+//! The core HumanRegex struct and the marker types that describe its state
+
 use regex::Regex;
 
 pub(crate) use std::fmt;
@@ -5,26 +7,33 @@ use std::marker::PhantomData as pd;
 use std::ops::Add;
 
 /// Represents the state when [HumanRegex] is a wrapper for a standard single-character class (the kind that starts with a backslash followed by a letter)
+#[derive(Debug)]
 pub struct Standard;
 
 /// Represents the state when [HumanRegex] is a wrapper for a custom single-character class (the kind surrounded by one layer of square brackets)
+#[derive(Debug)]
 pub struct Custom;
 
 /// Represents the state when [HumanRegex] is a wrapper for a single-character ASCII class (the kind surrounded by colons and two layers of square brackets)
+#[derive(Debug)]
 pub struct Ascii;
 
 /// Represents the state when [HumanRegex] is a wrapper for any type of single-character class
+#[derive(Debug)]
 pub struct SymbolClass<T>(std::marker::PhantomData<T>);
 
 /// Represents the state when [HumanRegex] is a wrapper for a literal string of characters
+#[derive(Debug)]
 pub struct LiteralSymbolChain;
 
 /// Represents the state when [HumanRegex] is a wrapper for any arbitrary regular expression
+#[derive(Debug)]
 pub struct SymbolChain;
 
 /// Represents the state when [HumanRegex] is a wrapper for a quantifier (e.g., an expression that
 /// matches a given number of a target). Importantly, these expressions are greedy by default and
 /// can be converted to a lazy match with the [lazy] method.
+#[derive(Debug)]
 pub struct Quantifier;
 
 /// The HumanRegex struct which maintains and updates the regex string. For most use cases it will
@@ -35,7 +44,7 @@ pub struct HumanRegex<T = SymbolChain>(pub String, pub std::marker::PhantomData<
 impl<T> HumanRegex<T> {
     /// Convert to a rust Regex
     pub fn to_regex(&self) -> Regex {
-        Regex::new(&*self.0).unwrap()
+        Regex::new(&self.0).unwrap()
     }
 }
 
@@ -64,10 +73,47 @@ impl<T, U> Add<HumanRegex<U>> for HumanRegex<T> {
     type Output = HumanRegex<SymbolChain>;
 
     fn add(self, rhs: HumanRegex<U>) -> Self::Output {
-        HumanRegex(
-            format!("{}{}", self.to_string(), rhs.to_string()),
-            pd::<SymbolChain>,
-        )
+        HumanRegex(format!("{}{}", self, rhs), pd::<SymbolChain>)
+    }
+}
+
+/// Concatenation with a borrowed [HumanRegex], so that a capture group can still be used to look up
+/// its match afterwards
+/// ```
+/// use human_regex::{digit, named_capture, one_or_more, text};
+/// let number = named_capture(one_or_more(digit()), "number");
+/// let regex_string = text("#") + &number;
+/// let caps = regex_string.captures("issue #42").unwrap();
+/// assert_eq!(caps.get(&number).unwrap().as_str(), "42");
+/// ```
+impl<T, U> Add<&HumanRegex<U>> for HumanRegex<T> {
+    type Output = HumanRegex<SymbolChain>;
+
+    fn add(self, rhs: &HumanRegex<U>) -> Self::Output {
+        HumanRegex(format!("{}{}", self, rhs), pd::<SymbolChain>)
+    }
+}
+
+impl<T, U> Add<HumanRegex<U>> for &HumanRegex<T> {
+    type Output = HumanRegex<SymbolChain>;
+
+    fn add(self, rhs: HumanRegex<U>) -> Self::Output {
+        HumanRegex(format!("{}{}", self, rhs), pd::<SymbolChain>)
+    }
+}
+
+impl<T, U> Add<&HumanRegex<U>> for &HumanRegex<T> {
+    type Output = HumanRegex<SymbolChain>;
+
+    fn add(self, rhs: &HumanRegex<U>) -> Self::Output {
+        HumanRegex(format!("{}{}", self, rhs), pd::<SymbolChain>)
+    }
+}
+
+// Implemented by hand so that the marker type does not have to be Clone
+impl<T> Clone for HumanRegex<T> {
+    fn clone(&self) -> Self {
+        HumanRegex(self.0.clone(), pd::<T>)
     }
 }
 
@@ -0,0 +1,331 @@
+//! Functions for converting glob and SQL `LIKE` patterns into regular expressions
+//!
+//! Both kinds of pattern have to match the whole input, so the results are anchored with
+//! [beginning](crate::beginning) and [end](crate::end). Their wildcards match newlines too.
+
+use super::humanregex::*;
+use super::{
+    any, beginning, dot_matches_newline_too, end, or, subtract, text, within_set, without_set,
+    zero_or_more, zero_or_one,
+};
+use regex::escape;
+use std::marker::PhantomData as pd;
+
+/// An error in a glob, along with where it happened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobError {
+    /// The column of the error in characters, starting from 1
+    pub column: usize,
+    /// A description of what went wrong
+    pub message: String,
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for GlobError {}
+
+/// Options for [from_glob_with]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlobOptions {
+    /// Whether `*`, `?` and `[...]` stop at the `/` path separator. When they do, `**` matches
+    /// across directories: `**/` matches any number of leading directories and a trailing `/**`
+    /// matches everything inside a directory.
+    pub literal_separator: bool,
+    /// Whether a backslash escapes the character after it rather than being matched literally
+    pub backslash_escape: bool,
+}
+
+impl Default for GlobOptions {
+    fn default() -> Self {
+        GlobOptions {
+            literal_separator: false,
+            backslash_escape: true,
+        }
+    }
+}
+
+/// Converts a shell-style glob into a regular expression, supporting `*`, `?`, `[...]` classes
+/// (negated with `!` or `^`) and `{a,b}` alternatives. Wildcards match any character, including
+/// `/`; use [from_glob_with] for path-separator semantics.
+/// ```
+/// use human_regex::from_glob;
+/// let regex_string = from_glob("*.{rs,toml}").unwrap();
+/// assert!(regex_string.to_regex().is_match("lib.rs"));
+/// assert!(regex_string.to_regex().is_match("src/Cargo.toml"));
+/// assert!(!regex_string.to_regex().is_match("lib.rs.bak"));
+///
+/// assert_eq!(from_glob("file[z-a]").unwrap_err().to_string(), "column 6: the range `z-a` is reversed");
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a class contains a range whose start comes after its end.
+pub fn from_glob(pattern: &str) -> Result<HumanRegex<SymbolChain>, GlobError> {
+    from_glob_with(pattern, GlobOptions::default())
+}
+
+/// Converts a shell-style glob into a regular expression with the given [GlobOptions]
+/// ```
+/// use human_regex::{from_glob_with, GlobOptions};
+/// let options = GlobOptions { literal_separator: true, ..GlobOptions::default() };
+/// let regex_string = from_glob_with("src/**/*.rs", options).unwrap();
+/// assert!(regex_string.to_regex().is_match("src/lib.rs"));
+/// assert!(regex_string.to_regex().is_match("src/bin/main.rs"));
+/// assert!(!regex_string.to_regex().is_match("src/lib.rs/notes.txt"));
+/// assert!(!from_glob_with("*.rs", options).unwrap().to_regex().is_match("src/lib.rs"));
+/// assert!(!from_glob_with("a[+-0]b", options).unwrap().to_regex().is_match("a/b"));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a class contains a range whose start comes after its end.
+pub fn from_glob_with(
+    pattern: &str,
+    options: GlobOptions,
+) -> Result<HumanRegex<SymbolChain>, GlobError> {
+    let mut glob = Glob {
+        chars: pattern.chars().collect(),
+        index: 0,
+        options,
+    };
+    Ok(dot_matches_newline_too(
+        beginning() + glob.sequence(false)? + end(),
+    ))
+}
+
+/// Converts a SQL `LIKE` pattern into a regular expression, where `%` matches any run of
+/// characters and `_` matches exactly one. Use [from_sql_like_with_escape] for patterns that
+/// declare an `ESCAPE` character.
+/// ```
+/// use human_regex::from_sql_like;
+/// let regex_string = from_sql_like("abc%_");
+/// assert!(regex_string.to_regex().is_match("abcd"));
+/// assert!(regex_string.to_regex().is_match("abc.def"));
+/// assert!(!regex_string.to_regex().is_match("abc"));
+/// ```
+pub fn from_sql_like(pattern: &str) -> HumanRegex<SymbolChain> {
+    sql_like(pattern, None)
+}
+
+/// Converts a SQL `LIKE` pattern into a regular expression, where the `escape` character makes
+/// the character after it match literally, as in `LIKE '100\%' ESCAPE '\'`
+/// ```
+/// use human_regex::from_sql_like_with_escape;
+/// let regex_string = from_sql_like_with_escape(r"100\%%", '\\');
+/// assert!(regex_string.to_regex().is_match("100% cotton"));
+/// assert!(!regex_string.to_regex().is_match("1000"));
+/// ```
+pub fn from_sql_like_with_escape(pattern: &str, escape: char) -> HumanRegex<SymbolChain> {
+    sql_like(pattern, Some(escape))
+}
+
+/// Converts a SQL `LIKE` pattern with an optional escape character
+fn sql_like(pattern: &str, escape: Option<char>) -> HumanRegex<SymbolChain> {
+    let mut sequence = Sequence::default();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            _ if Some(c) == escape => sequence.literal(chars.next().unwrap_or(c)),
+            '%' => sequence.push(zero_or_more(any())),
+            '_' => sequence.push(any()),
+            _ => sequence.literal(c),
+        }
+    }
+    dot_matches_newline_too(beginning() + sequence.finish() + end())
+}
+
+/// A regex under construction, gathering runs of literal characters into a single [text] call
+#[derive(Default)]
+struct Sequence {
+    /// The finished part of the regex
+    regex_string: String,
+    /// Literal characters that have not been added yet
+    literal: String,
+}
+
+impl Sequence {
+    /// Add a character to be matched literally
+    fn literal(&mut self, c: char) {
+        self.literal.push(c);
+    }
+
+    /// Add a pattern after any pending literal characters
+    fn push<T>(&mut self, target: HumanRegex<T>) {
+        self.flush();
+        self.regex_string.push_str(&target.0);
+    }
+
+    /// Add any pending literal characters
+    fn flush(&mut self) {
+        if !self.literal.is_empty() {
+            self.regex_string
+                .push_str(&text(std::mem::take(&mut self.literal)).0);
+        }
+    }
+
+    /// The completed regex
+    fn finish(mut self) -> HumanRegex<SymbolChain> {
+        self.flush();
+        HumanRegex(self.regex_string, pd::<SymbolChain>)
+    }
+}
+
+/// A glob being converted, one character at a time
+struct Glob {
+    /// The characters of the glob
+    chars: Vec<char>,
+    /// The position of the next character to convert
+    index: usize,
+    /// How wildcards treat path separators and backslashes
+    options: GlobOptions,
+}
+
+impl Glob {
+    /// The character at an offset from the current position
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+
+    /// Convert characters until the end of the glob or, inside braces, until a `,` or `}`
+    fn sequence(&mut self, in_braces: bool) -> Result<HumanRegex<SymbolChain>, GlobError> {
+        let mut sequence = Sequence::default();
+        while let Some(c) = self.peek(0) {
+            if in_braces && (c == ',' || c == '}') {
+                break;
+            }
+            self.index += 1;
+            match c {
+                '\\' if self.options.backslash_escape => match self.peek(0) {
+                    Some(escaped) => {
+                        self.index += 1;
+                        sequence.literal(escaped)
+                    }
+                    None => sequence.literal(c),
+                },
+                '*' => sequence.push(self.star()),
+                '?' if self.options.literal_separator => sequence.push(without_set(&["/"])),
+                '?' => sequence.push(any()),
+                '[' => match self.class()? {
+                    Some(class) => sequence.push(class),
+                    None => sequence.literal(c),
+                },
+                '{' => match self.alternatives()? {
+                    Some(alternatives) => sequence.push(alternatives),
+                    None => sequence.literal(c),
+                },
+                _ => sequence.literal(c),
+            }
+        }
+        Ok(sequence.finish())
+    }
+
+    /// Convert a `*` or `**` wildcard, whose first `*` has been consumed
+    fn star(&mut self) -> HumanRegex<SymbolChain> {
+        let mut stars = 1;
+        while self.peek(0) == Some('*') {
+            self.index += 1;
+            stars += 1;
+        }
+        if !self.options.literal_separator {
+            return HumanRegex(zero_or_more(any()).0, pd::<SymbolChain>);
+        }
+        let whole_segment = self.index == stars || self.chars[self.index - stars - 1] == '/';
+        match self.peek(0) {
+            Some('/') if stars > 1 && whole_segment => {
+                self.index += 1;
+                HumanRegex(
+                    zero_or_one(zero_or_more(any()) + text("/")).0,
+                    pd::<SymbolChain>,
+                )
+            }
+            None if stars > 1 && whole_segment => {
+                HumanRegex(zero_or_more(any()).0, pd::<SymbolChain>)
+            }
+            _ => HumanRegex(zero_or_more(without_set(&["/"])).0, pd::<SymbolChain>),
+        }
+    }
+
+    /// Convert a `[...]` class whose `[` has been consumed, or return [None] if it is never closed.
+    /// When separators are literal, the class never matches `/`.
+    fn class(&mut self) -> Result<Option<HumanRegex<SymbolChain>>, GlobError> {
+        let start = self.index;
+        let negated = matches!(self.peek(0), Some('!' | '^'));
+        if negated {
+            self.index += 1;
+        }
+        let mut members = Vec::new();
+        loop {
+            let c = match self.peek(0) {
+                // A `]` right at the start is a member rather than the end of the class
+                Some(']') if !members.is_empty() => break,
+                Some(c) => c,
+                None => {
+                    self.index = start;
+                    return Ok(None);
+                }
+            };
+            self.index += 1;
+            match (self.peek(0), self.peek(1)) {
+                (Some('-'), Some(last)) if last != ']' => {
+                    if c > last {
+                        return Err(GlobError {
+                            column: self.index,
+                            message: format!("the range `{}-{}` is reversed", c, last),
+                        });
+                    }
+                    self.index += 2;
+                    members.push(format!(
+                        "{}-{}",
+                        escape(&c.to_string()),
+                        escape(&last.to_string())
+                    ));
+                }
+                _ => members.push(escape(&c.to_string())),
+            }
+        }
+        self.index += 1;
+        Ok(Some(if negated {
+            if self.options.literal_separator {
+                members.push("/".to_string());
+            }
+            HumanRegex(without_set(&members).0, pd::<SymbolChain>)
+        } else if self.options.literal_separator {
+            HumanRegex(
+                subtract(within_set(&members), within_set(&["/"])).0,
+                pd::<SymbolChain>,
+            )
+        } else {
+            HumanRegex(within_set(&members).0, pd::<SymbolChain>)
+        }))
+    }
+
+    /// Convert `{a,b}` alternatives whose `{` has been consumed, or return [None] if they are
+    /// never closed
+    fn alternatives(&mut self) -> Result<Option<HumanRegex<SymbolChain>>, GlobError> {
+        let start = self.index;
+        let mut options = vec![self.sequence(true)?];
+        loop {
+            match self.peek(0) {
+                Some(',') => {
+                    self.index += 1;
+                    options.push(self.sequence(true)?);
+                }
+                Some('}') => {
+                    self.index += 1;
+                    return Ok(Some(or(&options
+                        .iter()
+                        .map(|o| o.0.clone())
+                        .collect::<Vec<_>>())));
+                }
+                _ => {
+                    self.index = start;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
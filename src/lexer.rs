@@ -0,0 +1,257 @@
+//! Functions for splitting text into tokens with a set of named rules
+//!
+//! At each position every rule matches as much text as it can, and the rule with the longest match
+//! is used. When several rules match the same text, the one with the highest priority, and then
+//! the one added first, is used. Text that no rule matches becomes an error token, and lexing
+//! carries on after it.
+//! ```
+//! use human_regex::{digit, one_or_more, or, text, whitespace, word, Lexer, TokenKind};
+//! let lexer = Lexer::new()
+//!     .token("number", one_or_more(digit()))
+//!     .token("name", one_or_more(word()))
+//!     .token_with_priority("keyword", or(&["let", "in"]), 1)
+//!     .token("operator", or(&[text("="), text("+")]))
+//!     .skip(one_or_more(whitespace()));
+//!
+//! let tokens: Vec<_> = lexer.tokens("let x = 1 + ? in xs").collect();
+//! assert_eq!(tokens, vec![
+//!     (TokenKind::Token("keyword"), "let", 0..3),
+//!     (TokenKind::Token("name"), "x", 4..5),
+//!     (TokenKind::Token("operator"), "=", 6..7),
+//!     (TokenKind::Token("number"), "1", 8..9),
+//!     (TokenKind::Token("operator"), "+", 10..11),
+//!     (TokenKind::Error, "?", 12..13),
+//!     (TokenKind::Token("keyword"), "in", 14..16),
+//!     (TokenKind::Token("name"), "xs", 17..19),
+//! ]);
+//! ```
+
+use super::humanregex::*;
+use regex_automata::{meta, Anchored, Input, MatchKind, PatternSet};
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// The byte offsets of a token in the text
+pub type Span = Range<usize>;
+
+/// What a token is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind<'l> {
+    /// Text matched by the rule with the given name
+    Token(&'l str),
+    /// Text that no rule matches
+    Error,
+}
+
+/// A rule of a [Lexer]
+#[derive(Debug, Clone)]
+struct Rule {
+    /// The name of the tokens the rule makes, or `None` for text that is skipped
+    name: Option<String>,
+    /// Which rule is used when several match the same text, higher first
+    priority: i32,
+    /// The regex string of the rule
+    pattern: String,
+}
+
+/// The rules of a [Lexer], compiled to find the longest match of each
+#[derive(Debug, Clone)]
+struct Compiled {
+    /// Every rule, for finding the ones that match at a position in one pass
+    set: meta::Regex,
+    /// Each rule on its own, for finding the length of its longest match
+    rules: Vec<meta::Regex>,
+}
+
+/// A set of named token rules and skip rules, which splits text into tokens
+#[derive(Debug, Clone, Default)]
+pub struct Lexer {
+    /// The rules, in the order they were added
+    rules: Vec<Rule>,
+    /// The compiled rules, built the first time text is split into tokens
+    compiled: OnceLock<Compiled>,
+}
+
+impl Lexer {
+    /// A lexer without any rules, which makes all text an error token
+    pub fn new() -> Self {
+        Lexer {
+            rules: Vec::new(),
+            compiled: OnceLock::new(),
+        }
+    }
+
+    /// Add a rule for tokens with the given name, with a priority of 0
+    /// ```
+    /// use human_regex::{or, Lexer, TokenKind};
+    /// let lexer = Lexer::new().token("x", or(&["a", "ab"]));
+    /// let tokens: Vec<_> = lexer.tokens("ab").collect();
+    /// assert_eq!(tokens, vec![(TokenKind::Token("x"), "ab", 0..2)]);
+    /// ```
+    pub fn token<T>(self, name: &str, pattern: HumanRegex<T>) -> Self {
+        self.token_with_priority(name, pattern, 0)
+    }
+
+    /// Add a rule for tokens with the given name and priority, so that when it matches the same
+    /// text as another rule the one with the higher priority is used
+    /// ```
+    /// use human_regex::{one_or_more, text, word, Lexer, TokenKind};
+    /// let lexer = Lexer::new()
+    ///     .token("name", one_or_more(word()))
+    ///     .token_with_priority("keyword", text("if"), 1);
+    /// let kinds: Vec<_> = lexer.tokens("if iffy").map(|(kind, _, _)| kind).collect();
+    /// assert_eq!(kinds, vec![TokenKind::Token("keyword"), TokenKind::Error, TokenKind::Token("name")]);
+    /// ```
+    pub fn token_with_priority<T>(self, name: &str, pattern: HumanRegex<T>, priority: i32) -> Self {
+        self.add(Some(name.to_string()), pattern.0, priority)
+    }
+
+    /// Add a rule for text that is skipped, such as whitespace or comments
+    /// ```
+    /// use human_regex::{digit, one_or_more, text, whitespace, without_set, zero_or_more, Lexer};
+    /// let lexer = Lexer::new()
+    ///     .token("number", one_or_more(digit()))
+    ///     .skip(one_or_more(whitespace()))
+    ///     .skip(text("#") + zero_or_more(without_set(&[r"\n"])));
+    /// let numbers: Vec<&str> = lexer.tokens("1 2 # 3\n4").map(|(_, text, _)| text).collect();
+    /// assert_eq!(numbers, vec!["1", "2", "4"]);
+    /// ```
+    pub fn skip<T>(self, pattern: HumanRegex<T>) -> Self {
+        self.add(None, pattern.0, 0)
+    }
+
+    /// Add a rule, discarding any rules compiled before it
+    fn add(mut self, name: Option<String>, pattern: String, priority: i32) -> Self {
+        self.rules.push(Rule {
+            name,
+            priority,
+            pattern,
+        });
+        self.compiled = OnceLock::new();
+        self
+    }
+
+    /// The tokens of the text, with the kind, text and span of each. A rule that matches nothing is
+    /// not used. Anchors and word boundaries in a rule see the text around the token, as they
+    /// would in a search of the whole text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a rule is not a valid regex.
+    pub fn tokens<'l, 'h>(&'l self, haystack: &'h str) -> Tokens<'l, 'h> {
+        Tokens {
+            lexer: self,
+            haystack,
+            position: 0,
+        }
+    }
+
+    /// The rules, compiled the first time they are needed
+    fn compiled(&self) -> &Compiled {
+        self.compiled.get_or_init(|| {
+            let config = || meta::Config::new().match_kind(MatchKind::All);
+            let patterns: Vec<&str> = self
+                .rules
+                .iter()
+                .map(|rule| rule.pattern.as_str())
+                .collect();
+            Compiled {
+                set: meta::Regex::builder()
+                    .configure(config())
+                    .build_many(&patterns)
+                    .unwrap(),
+                rules: patterns
+                    .iter()
+                    .map(|pattern| {
+                        meta::Regex::builder()
+                            .configure(config())
+                            .build(pattern)
+                            .unwrap()
+                    })
+                    .collect(),
+            }
+        })
+    }
+
+    /// The rule to use at a position in the text and the length of its match, if any rule matches
+    fn longest_match(&self, haystack: &str, position: usize) -> Option<(&Rule, usize)> {
+        let compiled = self.compiled();
+        let input = Input::new(haystack)
+            .anchored(Anchored::Yes)
+            .range(position..);
+        let mut matching = PatternSet::new(compiled.set.pattern_len());
+        compiled
+            .set
+            .which_overlapping_matches(&input, &mut matching);
+        matching
+            .iter()
+            .filter_map(|pattern| {
+                let index = pattern.as_usize();
+                let length = compiled.rules[index].search(&input)?.end() - position;
+                (length > 0).then_some((index, length))
+            })
+            .max_by(|(a, a_length), (b, b_length)| {
+                a_length
+                    .cmp(b_length)
+                    .then(self.rules[*a].priority.cmp(&self.rules[*b].priority))
+                    .then(b.cmp(a))
+            })
+            .map(|(index, length)| (&self.rules[index], length))
+    }
+}
+
+/// An iterator over the tokens of a text, made by [Lexer::tokens]
+#[derive(Debug, Clone)]
+pub struct Tokens<'l, 'h> {
+    /// The lexer whose rules are used
+    lexer: &'l Lexer,
+    /// The text being split into tokens
+    haystack: &'h str,
+    /// The offset of the text that is left
+    position: usize,
+}
+
+impl<'l, 'h> Iterator for Tokens<'l, 'h> {
+    type Item = (TokenKind<'l>, &'h str, Span);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut error_start = None;
+        while self.position < self.haystack.len() {
+            match self.lexer.longest_match(self.haystack, self.position) {
+                Some((rule, length)) => {
+                    if let Some(start) = error_start {
+                        return Some((
+                            TokenKind::Error,
+                            &self.haystack[start..self.position],
+                            start..self.position,
+                        ));
+                    }
+                    let start = self.position;
+                    self.position += length;
+                    if let Some(name) = &rule.name {
+                        return Some((
+                            TokenKind::Token(name),
+                            &self.haystack[start..self.position],
+                            start..self.position,
+                        ));
+                    }
+                }
+                None => {
+                    error_start.get_or_insert(self.position);
+                    self.position += self.haystack[self.position..]
+                        .chars()
+                        .next()
+                        .unwrap()
+                        .len_utf8();
+                }
+            }
+        }
+        error_start.map(|start| {
+            (
+                TokenKind::Error,
+                &self.haystack[start..],
+                start..self.haystack.len(),
+            )
+        })
+    }
+}
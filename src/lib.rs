@@ -5,6 +5,19 @@
 
 pub mod cookbook;
 
+pub mod dsl;
+
+pub mod tree;
+
+pub mod patterns;
+
+pub mod dialect;
+#[doc(inline)]
+pub use dialect::{Dialect, DialectError};
+
+#[cfg(feature = "serde")]
+mod serialization;
+
 mod humanregex;
 #[doc(inline)]
 pub use humanregex::HumanRegex;
@@ -40,3 +53,35 @@ pub use ascii::*;
 pub mod flags;
 #[doc(inline)]
 pub use flags::*;
+
+pub mod import;
+#[doc(inline)]
+pub use import::*;
+
+pub mod verification;
+#[doc(inline)]
+pub use verification::*;
+
+pub mod ranges;
+#[doc(inline)]
+pub use ranges::*;
+
+pub mod record;
+#[doc(inline)]
+pub use record::*;
+
+pub mod replacement;
+#[doc(inline)]
+pub use replacement::*;
+
+pub mod rewriter;
+#[doc(inline)]
+pub use rewriter::*;
+
+pub mod lexer;
+#[doc(inline)]
+pub use lexer::*;
+
+pub mod grammar;
+#[doc(inline)]
+pub use grammar::*;
@@ -16,11 +16,11 @@ pub fn or<T>(options: &[T]) -> HumanRegex<SymbolChain>
 where
     T: Into<String> + fmt::Display,
 {
-    let mut regex_string = format!("{}", options[0].to_string());
-    for idx in 1..options.len() {
-        regex_string = format!("{}|{}", regex_string, options[idx].to_string())
+    let mut regex_string = options[0].to_string();
+    for option in &options[1..] {
+        regex_string = format!("{}|{}", regex_string, option)
     }
-    HumanRegex(format!("(:?{})", regex_string), pd::<SymbolChain>)
+    HumanRegex(format!("(?:{})", regex_string), pd::<SymbolChain>)
 }
 
 /// Xor on two [SymbolClass]es, also known as symmetric difference.
@@ -37,10 +37,7 @@ pub fn xor<T, U>(
     lhs: HumanRegex<SymbolClass<T>>,
     rhs: HumanRegex<SymbolClass<U>>,
 ) -> HumanRegex<SymbolClass<Custom>> {
-    HumanRegex(
-        format!("[{}~~{}]", lhs.to_string(), rhs.to_string()),
-        pd::<SymbolClass<Custom>>,
-    )
+    HumanRegex(format!("[{}~~{}]", lhs, rhs), pd::<SymbolClass<Custom>>)
 }
 
 /// A function for establishing an AND relationship between two or more possible matches
@@ -72,10 +69,7 @@ impl<T, U> std::ops::BitAnd<HumanRegex<SymbolClass<U>>> for HumanRegex<SymbolCla
     type Output = HumanRegex<SymbolClass<Custom>>;
 
     fn bitand(self, rhs: HumanRegex<SymbolClass<U>>) -> Self::Output {
-        HumanRegex(
-            format!("[{}&&{}]", self.to_string(), rhs.to_string()),
-            pd::<SymbolClass<Custom>>,
-        )
+        HumanRegex(format!("[{}&&{}]", self, rhs), pd::<SymbolClass<Custom>>)
     }
 }
 
@@ -93,7 +87,7 @@ pub fn subtract<T, U>(
     subtract: HumanRegex<SymbolClass<U>>,
 ) -> HumanRegex<SymbolClass<Custom>> {
     HumanRegex(
-        format!("[{}--{}]", from.to_string(), subtract.to_string()),
+        format!("[{}--{}]", from, subtract),
         pd::<SymbolClass<Custom>>,
     )
 }
@@ -195,7 +189,6 @@ impl std::ops::Not for HumanRegex<LiteralSymbolChain> {
         HumanRegex(
             self.to_string()
                 .chars()
-                .into_iter()
                 .map(|chr| format!("[^{}]", chr))
                 .collect::<String>(),
             pd::<SymbolChain>,
@@ -0,0 +1,58 @@
+//! Ready-made patterns for common kinds of data, built from the same functions as everything else
+//!
+//! Builders whose components are worth extracting have a `_with` variant that takes a [Naming],
+//! which decides whether those components get named capture groups.
+//! ```
+//! use human_regex::{beginning, end, patterns::network::ipv4_with, patterns::Naming};
+//! let regex_string = beginning() + ipv4_with(Naming::Prefixed("source")) + end();
+//! let caps = regex_string.to_regex().captures("10.0.0.254").unwrap();
+//! assert_eq!(&caps["source_octet4"], "254");
+//! ```
+
+use super::humanregex::*;
+use super::named_capture;
+use std::marker::PhantomData as pd;
+
+pub mod code;
+pub mod datetime;
+pub mod ids;
+pub mod locale;
+pub mod logs;
+pub mod markup;
+pub mod network;
+pub mod numbers;
+pub mod paths;
+pub mod version;
+pub mod web;
+
+#[doc(inline)]
+pub use version::{cargo_version_req, npm_version_range, semver, semver_with};
+
+/// How a pattern builder names the capture groups around its components
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Naming<'a> {
+    /// No capture groups are added
+    #[default]
+    Unnamed,
+    /// Components are captured under their own names, such as `octet1`
+    Named,
+    /// Components are captured under their names with a prefix, such as `source_octet1`, so that
+    /// the same pattern can be used more than once in a regex
+    Prefixed(&'a str),
+}
+
+impl Naming<'_> {
+    /// Wrap a component in a capture group with the given name, if capture groups are wanted
+    pub(crate) fn capture<T>(self, name: &str, target: HumanRegex<T>) -> HumanRegex<SymbolChain> {
+        match self {
+            Naming::Unnamed => chain(target),
+            Naming::Named => named_capture(target, name),
+            Naming::Prefixed(prefix) => named_capture(target, &format!("{}_{}", prefix, name)),
+        }
+    }
+}
+
+/// Forget what kind of expression a [HumanRegex] holds, so that different kinds can be collected together
+pub(crate) fn chain<T>(target: HumanRegex<T>) -> HumanRegex<SymbolChain> {
+    HumanRegex(target.0, pd::<SymbolChain>)
+}
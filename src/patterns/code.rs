@@ -0,0 +1,507 @@
+//! Patterns for the tokens of programming languages: identifiers, string literals and comments
+//!
+//! These are meant for lightweight tools such as syntax highlighters and linters, so each token is
+//! matched on its own, without regard for the code around it.
+//! ```
+//! use human_regex::{or, patterns::code::{block_comment, identifier, line_comment, string_literal, Language}};
+//! let token = or(&[
+//!     line_comment(Language::Rust),
+//!     block_comment(Language::Rust).unwrap(),
+//!     string_literal(Language::Rust),
+//!     identifier(Language::Rust),
+//! ]);
+//! let tokens: Vec<&str> = token
+//!     .to_regex()
+//!     .find_iter(r#"let größe = "a \"b\""; // the size"#)
+//!     .map(|m| m.as_str())
+//!     .collect();
+//! assert_eq!(tokens, vec!["let", "größe", r#""a \"b\"""#, "// the size"]);
+//! ```
+
+use super::chain;
+use crate::humanregex::*;
+use crate::{
+    any, beginning, case_insensitive, dot_matches_newline_too, end, one_or_more, or, subtract,
+    text, unicode_category, within_set, without_set, word_boundary, zero_or_more, zero_or_one,
+    UnicodeCategory,
+};
+use std::collections::BTreeMap;
+
+/// The programming language whose rules a token follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// Identifiers made of Unicode identifier characters, and nested block comments
+    Rust,
+    /// Identifiers made of Unicode identifier characters, and strings with prefixes and triple quotes
+    Python,
+    /// Identifiers made of Unicode identifier characters along with `$`, and template literals
+    JavaScript,
+    /// Identifiers made of ASCII letters, digits and underscores
+    C,
+}
+
+/// How many levels deep [block_comment] follows comments nested inside Rust block comments
+const NESTING: usize = 4;
+
+/// How many `#` marks [string_literal] allows around a Rust raw string
+const RAW_HASHES: usize = 6;
+
+/// The characters that can start an identifier
+fn start_class(language: Language) -> HumanRegex<SymbolClass<Custom>> {
+    let start = unicode_category(UnicodeCategory::IdentifierStart).to_string();
+    match language {
+        Language::Rust | Language::Python => within_set(&[start, "_".to_string()]),
+        Language::JavaScript => within_set(&[start, r"\$".to_string(), "_".to_string()]),
+        Language::C => within_set(&["A-Z", "a-z", "_"]),
+    }
+}
+
+/// The characters that can appear in an identifier after the first
+fn continue_class(language: Language) -> HumanRegex<SymbolClass<Custom>> {
+    let continuing = unicode_category(UnicodeCategory::IdentifierContinue).to_string();
+    match language {
+        Language::Rust | Language::Python => within_set(&[continuing]),
+        // Zero-width non-joiners and joiners are allowed for scripts that need them
+        Language::JavaScript => within_set(&[
+            continuing,
+            r"\$".to_string(),
+            r"\x{200C}".to_string(),
+            r"\x{200D}".to_string(),
+        ]),
+        Language::C => within_set(&["0-9", "A-Z", "a-z", "_"]),
+    }
+}
+
+/// Matches an identifier, such as `snake_case` or `größe`
+/// ```
+/// use human_regex::{beginning, end, patterns::code::{identifier, Language}};
+/// let rust = beginning() + identifier(Language::Rust) + end();
+/// assert!(rust.to_regex().is_match("r#match"));
+/// assert!(rust.to_regex().is_match("_unused"));
+/// assert!(!rust.to_regex().is_match("_"));
+///
+/// let javascript = beginning() + identifier(Language::JavaScript) + end();
+/// assert!(javascript.to_regex().is_match("$élément"));
+/// assert!(!javascript.to_regex().is_match("2fast"));
+///
+/// let c = beginning() + identifier(Language::C) + end();
+/// assert!(c.to_regex().is_match("_Bool"));
+/// assert!(!c.to_regex().is_match("größe"));
+/// ```
+pub fn identifier(language: Language) -> HumanRegex<SymbolChain> {
+    match language {
+        Language::Rust => zero_or_one(text("r#")) + unprefixed(language),
+        _ => unprefixed(language),
+    }
+}
+
+/// Matches an identifier without the `r#` that marks a Rust raw identifier
+fn unprefixed(language: Language) -> HumanRegex<SymbolChain> {
+    match language {
+        // A lone `_` is a wildcard pattern rather than an identifier
+        Language::Rust => or(&[
+            unicode_category(UnicodeCategory::IdentifierStart)
+                + zero_or_more(continue_class(language)),
+            text("_") + one_or_more(continue_class(language)),
+        ]),
+        _ => start_class(language) + zero_or_more(continue_class(language)),
+    }
+}
+
+/// The reserved words of a language, which cannot be used as identifiers
+/// ```
+/// use human_regex::patterns::code::{keywords, Language};
+/// assert!(keywords(Language::Python).contains(&"lambda"));
+/// assert!(!keywords(Language::Python).contains(&"match"));
+/// ```
+///
+/// Words that are only keywords in some contexts, such as `union` in Rust or `match` in Python,
+/// are left out.
+pub fn keywords(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Rust => &[
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+            "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+            "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+            "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become",
+            "box", "do", "final", "gen", "macro", "override", "priv", "try", "typeof", "unsized",
+            "virtual", "yield",
+        ],
+        Language::Python => &[
+            "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+            "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+            "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise",
+            "return", "try", "while", "with", "yield",
+        ],
+        Language::JavaScript => &[
+            "await",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "debugger",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "export",
+            "extends",
+            "false",
+            "finally",
+            "for",
+            "function",
+            "if",
+            "implements",
+            "import",
+            "in",
+            "instanceof",
+            "interface",
+            "let",
+            "new",
+            "null",
+            "package",
+            "private",
+            "protected",
+            "public",
+            "return",
+            "static",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "true",
+            "try",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "with",
+            "yield",
+        ],
+        Language::C => &[
+            "alignas",
+            "alignof",
+            "auto",
+            "bool",
+            "break",
+            "case",
+            "char",
+            "const",
+            "constexpr",
+            "continue",
+            "default",
+            "do",
+            "double",
+            "else",
+            "enum",
+            "extern",
+            "false",
+            "float",
+            "for",
+            "goto",
+            "if",
+            "inline",
+            "int",
+            "long",
+            "nullptr",
+            "register",
+            "restrict",
+            "return",
+            "short",
+            "signed",
+            "sizeof",
+            "static",
+            "static_assert",
+            "struct",
+            "switch",
+            "thread_local",
+            "true",
+            "typedef",
+            "typeof",
+            "typeof_unqual",
+            "union",
+            "unsigned",
+            "void",
+            "volatile",
+            "while",
+            "_Alignas",
+            "_Alignof",
+            "_Atomic",
+            "_BitInt",
+            "_Bool",
+            "_Complex",
+            "_Decimal128",
+            "_Decimal32",
+            "_Decimal64",
+            "_Generic",
+            "_Imaginary",
+            "_Noreturn",
+            "_Static_assert",
+            "_Thread_local",
+        ],
+    }
+}
+
+/// The ASCII characters that can start an identifier, or appear in it after the first
+fn ascii_class(language: Language, first: bool) -> HumanRegex<SymbolClass<Custom>> {
+    let mut members = vec!["A-Z", "a-z", "_"];
+    if !first {
+        members.push("0-9");
+    }
+    if language == Language::JavaScript {
+        members.push(r"\$");
+    }
+    within_set(&members)
+}
+
+/// The characters outside ASCII that can start an identifier, or appear in it after the first
+fn non_ascii_class(language: Language, first: bool) -> HumanRegex<SymbolClass<Custom>> {
+    let class = if first {
+        start_class(language)
+    } else {
+        continue_class(language)
+    };
+    subtract(class, within_set(&[r"\x00-\x7F"]))
+}
+
+/// The words that an identifier must not be, arranged by their letters so that the identifiers
+/// that differ from all of them can be spelled out
+#[derive(Default)]
+struct Trie {
+    /// Whether a word ends here
+    terminal: bool,
+    /// The words that continue from here, by their next character
+    children: BTreeMap<char, Trie>,
+}
+
+impl Trie {
+    /// Add a word below this point
+    fn insert(&mut self, word: &str) {
+        let mut node = self;
+        for c in word.chars() {
+            node = node.children.entry(c).or_default();
+        }
+        node.terminal = true;
+    }
+
+    /// Matches a start of the words that stops with an ASCII character that no word has next
+    fn departing(&self, language: Language, root: bool) -> HumanRegex<SymbolChain> {
+        let letters: Vec<String> = self
+            .children
+            .keys()
+            .map(|c| regex::escape(&c.to_string()))
+            .collect();
+        let mut alternatives: Vec<HumanRegex<SymbolChain>> = self
+            .children
+            .iter()
+            .map(|(c, child)| text(c.to_string()) + child.departing(language, false))
+            .collect();
+        alternatives.push(if letters.is_empty() {
+            chain(ascii_class(language, root))
+        } else {
+            chain(subtract(ascii_class(language, root), within_set(&letters)))
+        });
+        or(&alternatives)
+    }
+
+    /// Matches a start of the words that is at least one character long
+    fn prefixes(&self) -> Option<HumanRegex<SymbolChain>> {
+        let alternatives: Vec<HumanRegex<SymbolChain>> = self
+            .children
+            .iter()
+            .map(|(c, child)| match child.prefixes() {
+                Some(rest) => text(c.to_string()) + zero_or_one(rest),
+                None => chain(text(c.to_string())),
+            })
+            .collect();
+        (!alternatives.is_empty()).then(|| or(&alternatives))
+    }
+
+    /// Matches a start of the words that is not a whole word, and is at least one character long
+    fn unfinished(&self, root: bool) -> Option<HumanRegex<SymbolChain>> {
+        let mut alternatives: Vec<HumanRegex<SymbolChain>> = self
+            .children
+            .iter()
+            .filter_map(|(c, child)| {
+                child
+                    .unfinished(false)
+                    .map(|rest| text(c.to_string()) + rest)
+            })
+            .collect();
+        if !root && !self.terminal {
+            alternatives.push(chain(text("")));
+        }
+        (!alternatives.is_empty()).then(|| or(&alternatives))
+    }
+}
+
+/// Matches an identifier that is none of the given words, such as the language's [keywords]
+/// ```
+/// use human_regex::patterns::code::{identifier_except, keywords, Language};
+/// let regex_string = identifier_except(Language::Rust, keywords(Language::Rust));
+/// let names: Vec<&str> = regex_string
+///     .to_regex()
+///     .find_iter("pub fn iffy(r#type: Type, self) -> Self")
+///     .map(|m| m.as_str())
+///     .collect();
+/// assert_eq!(names, vec!["iffy", "r#type", "Type"]);
+/// ```
+///
+/// As the regex crate cannot look ahead, the identifier has to start and end at word boundaries,
+/// so that the `i` of `if` is not matched on its own. This means that JavaScript identifiers that
+/// start or end with `$` are not matched.
+pub fn identifier_except(language: Language, words: &[&str]) -> HumanRegex<SymbolChain> {
+    let whole = (beginning() + identifier(language) + end()).to_regex();
+    let mut trie = Trie::default();
+    for word in words.iter().filter(|word| whole.is_match(word)) {
+        trie.insert(word);
+    }
+    // A lone `_` is not a Rust identifier
+    if language == Language::Rust {
+        trie.insert("_");
+    }
+    // An identifier is not one of the words if it leaves them at some character, whether in or
+    // out of ASCII, or if it stops before any of them ends
+    let leaving = match trie.prefixes() {
+        Some(prefixes) => or(&[
+            prefixes + non_ascii_class(language, false),
+            chain(non_ascii_class(language, true)),
+        ]),
+        None => chain(non_ascii_class(language, true)),
+    };
+    let mut alternatives = vec![
+        trie.departing(language, true) + zero_or_more(continue_class(language)),
+        leaving + zero_or_more(continue_class(language)),
+    ];
+    alternatives.extend(trie.unfinished(true));
+    // Raw identifiers are never keywords
+    if language == Language::Rust {
+        alternatives.insert(0, text("r#") + unprefixed(language));
+    }
+    word_boundary() + or(&alternatives) + word_boundary()
+}
+
+/// Any character, including a newline
+fn any_character() -> HumanRegex<SymbolChain> {
+    dot_matches_newline_too(any())
+}
+
+/// Matches the text between two quotes, where a `\` escapes the character after it
+fn escaped(quote: &str, newlines: bool) -> HumanRegex<SymbolChain> {
+    let plain = if newlines {
+        without_set(&[r"\\", quote])
+    } else {
+        without_set(&[r"\\", quote, r"\n"])
+    };
+    text(quote) + zero_or_more(or(&[chain(plain), text(r"\") + any_character()])) + text(quote)
+}
+
+/// Matches a string literal, including its quotes and any prefix, such as `"line\n"` or `r#"raw"#`
+/// ```
+/// use human_regex::{beginning, end, patterns::code::{string_literal, Language}};
+/// let rust = beginning() + string_literal(Language::Rust) + end();
+/// assert!(rust.to_regex().is_match(r#"b"bytes \"quoted\"""#));
+/// assert!(rust.to_regex().is_match(r###"r##"a "# inside"##"###));
+/// assert!(!rust.to_regex().is_match(r#""unterminated \""#));
+///
+/// let python = beginning() + string_literal(Language::Python) + end();
+/// assert!(python.to_regex().is_match("f'{name}'"));
+/// assert!(python.to_regex().is_match("Rb\"\"\"two\nlines\"\"\""));
+/// assert!(!python.to_regex().is_match("'two\nlines'"));
+///
+/// let javascript = beginning() + string_literal(Language::JavaScript) + end();
+/// assert!(javascript.to_regex().is_match("`two\nlines`"));
+/// ```
+///
+/// Rust raw strings may have up to six `#` marks, and the text of JavaScript template literals is
+/// matched without regard for the expressions inside `${}`.
+pub fn string_literal(language: Language) -> HumanRegex<SymbolChain> {
+    match language {
+        Language::Rust => {
+            let raw = (0..=RAW_HASHES)
+                .map(|count| {
+                    let hashes = "#".repeat(count);
+                    text(format!("{}\"", hashes))
+                        + zero_or_more(any_character()).lazy()
+                        + text(format!("\"{}", hashes))
+                })
+                .collect::<Vec<_>>();
+            zero_or_one(within_set(&['b', 'c'])) + or(&[escaped("\"", true), text("r") + or(&raw)])
+        }
+        Language::Python => {
+            let prefix = case_insensitive(or(&[
+                "rb", "br", "rf", "fr", "rt", "tr", "r", "u", "b", "f", "t",
+            ]));
+            let triple = |quote: &str| {
+                let quotes = quote.repeat(3);
+                text(&quotes)
+                    + zero_or_more(or(&[
+                        chain(without_set(&[r"\\"])),
+                        text(r"\") + any_character(),
+                    ]))
+                    .lazy()
+                    + text(&quotes)
+            };
+            zero_or_one(prefix)
+                + or(&[
+                    triple("\""),
+                    triple("'"),
+                    escaped("\"", false),
+                    escaped("'", false),
+                ])
+        }
+        Language::JavaScript => or(&[
+            escaped("\"", false),
+            escaped("'", false),
+            escaped("`", true),
+        ]),
+        Language::C => zero_or_one(or(&["u8", "u", "U", "L"])) + escaped("\"", false),
+    }
+}
+
+/// Matches a comment that runs to the end of the line, without the line break
+/// ```
+/// use human_regex::patterns::code::{line_comment, Language};
+/// let regex_string = line_comment(Language::Python);
+/// let comment = regex_string.to_regex().find("x = 1  # the answer\ny = 2").unwrap();
+/// assert_eq!(comment.as_str(), "# the answer");
+/// ```
+pub fn line_comment(language: Language) -> HumanRegex<SymbolChain> {
+    let marker = match language {
+        Language::Python => "#",
+        Language::Rust | Language::JavaScript | Language::C => "//",
+    };
+    text(marker) + zero_or_more(without_set(&[r"\n"]))
+}
+
+/// Matches a comment between `/*` and `*/`, or returns [None] for languages without them
+/// ```
+/// use human_regex::patterns::code::{block_comment, Language};
+/// let c = block_comment(Language::C).unwrap().to_regex();
+/// assert_eq!(c.find("/* a */ b */").unwrap().as_str(), "/* a */");
+///
+/// let rust = block_comment(Language::Rust).unwrap().to_regex();
+/// assert_eq!(rust.find("/* a /* b */ c */ d */").unwrap().as_str(), "/* a /* b */ c */");
+///
+/// assert!(block_comment(Language::Python).is_none());
+/// ```
+///
+/// Rust block comments can contain other block comments, which are followed up to four levels deep.
+pub fn block_comment(language: Language) -> Option<HumanRegex<SymbolChain>> {
+    let comment =
+        |inner: HumanRegex<SymbolChain>| text("/*") + zero_or_more(inner).lazy() + text("*/");
+    match language {
+        Language::Rust => {
+            let mut nested = comment(any_character());
+            for _ in 1..NESTING {
+                nested = comment(or(&[nested, any_character()]));
+            }
+            Some(nested)
+        }
+        Language::JavaScript | Language::C => Some(comment(any_character())),
+        Language::Python => None,
+    }
+}
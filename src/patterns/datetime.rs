@@ -0,0 +1,462 @@
+//! Patterns for dates and times, either in standard formats or described by a strftime string
+//!
+//! Each field only accepts the values it can have, such as `01` to `12` for a month, though the
+//! day is not checked against the length of the month. With [Naming::Named] the fields are
+//! captured as `year`, `month`, `day`, `hour`, `minute`, `second`, `fraction` and `offset`, along
+//! with `month_name`, `weekday`, `ordinal`, `week`, `am_pm`, `timezone` and `timestamp` where the
+//! format has them.
+//! ```
+//! use human_regex::{beginning, end, patterns::datetime::from_strftime_with, patterns::Naming};
+//! let regex_string = beginning() + from_strftime_with("%Y-%m-%d %H:%M", Naming::Named).unwrap() + end();
+//! let caps = regex_string.to_regex().captures("2023-10-05 14:30").unwrap();
+//! assert_eq!(&caps["month"], "10");
+//! assert_eq!(&caps["minute"], "30");
+//! assert!(!regex_string.to_regex().is_match("2023-13-05 14:30"));
+//! ```
+
+use super::{chain, Naming};
+use crate::humanregex::*;
+use crate::{
+    case_insensitive, exactly, number_in_range, number_in_range_with, one_or_more, or, text,
+    within_range, within_set, zero_or_one, NumberOptions,
+};
+use std::collections::HashSet;
+
+/// The full names of the months, in order
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// The full names of the days of the week, starting from Monday
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// An error in a strftime format string, along with where it happened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatError {
+    /// The column of the error in characters, starting from 1
+    pub column: usize,
+    /// A description of what went wrong
+    pub message: String,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// How a numeric field is padded to its full width
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Padding {
+    /// With leading zeros, as in `05`
+    Zero,
+    /// With leading spaces, as in ` 5`
+    Space,
+    /// Not at all, as in `5`
+    None,
+}
+
+/// A number from `lo` to `hi`, padded to `width` characters
+fn padded(lo: i64, hi: i64, width: u8, padding: Padding) -> HumanRegex<SymbolChain> {
+    match padding {
+        Padding::Zero => number_in_range_with(
+            lo..=hi,
+            NumberOptions {
+                width: Some(width),
+                ..Default::default()
+            },
+        ),
+        Padding::None => number_in_range(lo..=hi),
+        Padding::Space => {
+            // Numbers with fewer digits are preceded by one space for each missing digit
+            let mut alternatives = Vec::new();
+            for digits in (1..=width as u32).rev() {
+                let first = lo.max(if digits == 1 {
+                    0
+                } else {
+                    10i64.pow(digits - 1)
+                });
+                let last = hi.min(10i64.pow(digits) - 1);
+                if first <= last {
+                    let spaces = " ".repeat((width as u32 - digits) as usize);
+                    alternatives.push(text(spaces) + number_in_range(first..=last));
+                }
+            }
+            or(&alternatives)
+        }
+    }
+}
+
+/// Any of a list of names, in any case, or their first three letters when `abbreviated`
+fn names(list: &[&str], abbreviated: bool) -> HumanRegex<SymbolChain> {
+    let options: Vec<String> = list
+        .iter()
+        .map(|name| {
+            if abbreviated {
+                name[..3].to_string()
+            } else {
+                name.to_string()
+            }
+        })
+        .collect();
+    case_insensitive(or(&options))
+}
+
+/// A UTC offset written as `+hhmm`, or as `+hh:mm` when `colon` is set
+fn utc_offset(colon: bool) -> HumanRegex<SymbolChain> {
+    within_set(&['+', '-'])
+        + padded(0, 23, 2, Padding::Zero)
+        + if colon {
+            chain(text(":"))
+        } else {
+            chain(text(""))
+        }
+        + padded(0, 59, 2, Padding::Zero)
+}
+
+/// Converts a strftime or chrono format string into a pattern that matches the dates it produces.
+///
+/// The supported specifiers are `%Y %C %y %G %g %m %b %h %B %d %e %j %a %A %u %w %U %W %V %H %k %I %l
+/// %M %S %f %p %P %z %Z %s %F %T %R %D %n %t %%`. Numeric fields take the `-` flag to drop their
+/// padding, `_` to pad with spaces and `0` to pad with zeros, and `%f` takes a width and a leading
+/// `.` as in `%.3f`. `%:z` writes the offset with a colon.
+/// ```
+/// use human_regex::{beginning, end, patterns::datetime::from_strftime};
+/// let regex_string = beginning() + from_strftime("%a, %-d %b %Y").unwrap() + end();
+/// assert!(regex_string.to_regex().is_match("Thu, 5 Oct 2023"));
+/// assert!(!regex_string.to_regex().is_match("Thu, 05 Oct 2023"));
+///
+/// assert_eq!(from_strftime("%Y-%Q").unwrap_err().to_string(), "column 4: unknown specifier `%Q`");
+/// assert_eq!(from_strftime("%300f").unwrap_err().to_string(), "column 1: a width can be at most 255");
+/// ```
+pub fn from_strftime(format: &str) -> Result<HumanRegex<SymbolChain>, FormatError> {
+    from_strftime_with(format, Naming::Unnamed)
+}
+
+/// Converts a strftime or chrono format string into a pattern, naming the captures of its fields
+/// as the [module documentation](self) describes. A field that appears more than once is only
+/// captured the first time.
+/// ```
+/// use human_regex::{patterns::datetime::from_strftime_with, patterns::Naming};
+/// let regex_string = from_strftime_with("%d/%m/%Y %I:%M %p", Naming::Named).unwrap();
+/// let caps = regex_string.to_regex().captures("Due 05/10/2023 02:30 PM").unwrap();
+/// assert_eq!(&caps["day"], "05");
+/// assert_eq!(&caps["hour"], "02");
+/// assert_eq!(&caps["am_pm"], "PM");
+/// ```
+pub fn from_strftime_with(
+    format: &str,
+    naming: Naming,
+) -> Result<HumanRegex<SymbolChain>, FormatError> {
+    let mut captured = HashSet::new();
+    strftime(format, naming, &mut captured, 0)
+}
+
+/// Convert a format string, remembering which fields have been captured so far. `offset` is the
+/// column of the format string within the one the user wrote, for error messages.
+fn strftime(
+    format: &str,
+    naming: Naming,
+    captured: &mut HashSet<&'static str>,
+    offset: usize,
+) -> Result<HumanRegex<SymbolChain>, FormatError> {
+    let mut regex_string = chain(text(""));
+    let mut literal = String::new();
+    let mut chars = format.chars().enumerate().peekable();
+    while let Some((column, c)) = chars.next() {
+        if c != '%' {
+            literal.push(c);
+            continue;
+        }
+        let error = |message: String| FormatError {
+            column: offset + column + 1,
+            message,
+        };
+        // Flags, then a width, then the specifier itself
+        let mut spec = String::from("%");
+        let mut flag = None;
+        if let Some(&(_, found @ ('-' | '_' | '0' | '.' | ':'))) = chars.peek() {
+            chars.next();
+            spec.push(found);
+            flag = Some(found);
+        }
+        let padding = match flag {
+            Some('-') => Some(Padding::None),
+            Some('_') => Some(Padding::Space),
+            Some('0') => Some(Padding::Zero),
+            _ => None,
+        };
+        let mut width = None;
+        while let Some(&(_, digit @ '0'..='9')) = chars.peek() {
+            chars.next();
+            spec.push(digit);
+            width = Some(
+                width
+                    .unwrap_or(0u8)
+                    .checked_mul(10)
+                    .and_then(|width| width.checked_add(digit.to_digit(10).unwrap() as u8))
+                    .ok_or_else(|| error("a width can be at most 255".to_string()))?,
+            );
+        }
+        let Some((_, specifier)) = chars.next() else {
+            return Err(error(
+                "the format ends in the middle of a specifier".to_string(),
+            ));
+        };
+        spec.push(specifier);
+
+        // Specifiers that stand for other formats
+        let expansion = match specifier {
+            'F' => Some("%Y-%m-%d"),
+            'T' => Some("%H:%M:%S"),
+            'R' => Some("%H:%M"),
+            'D' => Some("%m/%d/%y"),
+            _ => None,
+        };
+        let numeric = |lo: i64, hi: i64, width: u8, default: Padding| {
+            padded(lo, hi, width, padding.unwrap_or(default))
+        };
+        let (name, field): (Option<&'static str>, HumanRegex<SymbolChain>) = match specifier {
+            _ if expansion.is_some() && spec.len() == 2 => {
+                let expanded = strftime(expansion.unwrap(), naming, captured, offset + column)?;
+                (None, expanded)
+            }
+            '%' | 'n' | 't' if spec.len() == 2 => {
+                literal.push(match specifier {
+                    'n' => '\n',
+                    't' => '\t',
+                    _ => '%',
+                });
+                continue;
+            }
+            'f' if matches!(flag, None | Some('.')) => {
+                let digits = match width {
+                    Some(width @ 1..=9) => chain(exactly(width, within_range('0'..='9'))),
+                    None => chain(one_or_more(within_range('0'..='9'))),
+                    Some(_) => return Err(error(format!("`{}` needs a width from 1 to 9", spec))),
+                };
+                if flag == Some('.') {
+                    let fraction = naming.capture("fraction", digits);
+                    literal_flush(&mut regex_string, &mut literal);
+                    regex_string = regex_string + zero_or_one(text(".") + fraction);
+                    continue;
+                }
+                (Some("fraction"), digits)
+            }
+            'z' if matches!(flag, None | Some(':')) && width.is_none() => {
+                (Some("offset"), utc_offset(flag == Some(':')))
+            }
+            _ if width.is_some() || matches!(flag, Some('.' | ':')) => {
+                return Err(error(format!("unsupported specifier `{}`", spec)))
+            }
+            'Y' | 'G' => (Some("year"), numeric(0, 9999, 4, Padding::Zero)),
+            'C' => (Some("century"), numeric(0, 99, 2, Padding::Zero)),
+            'y' | 'g' => (Some("year"), numeric(0, 99, 2, Padding::Zero)),
+            'm' => (Some("month"), numeric(1, 12, 2, Padding::Zero)),
+            'b' | 'h' => (Some("month_name"), names(&MONTHS, true)),
+            'B' => (Some("month_name"), names(&MONTHS, false)),
+            'd' => (Some("day"), numeric(1, 31, 2, Padding::Zero)),
+            'e' => (Some("day"), numeric(1, 31, 2, Padding::Space)),
+            'j' => (Some("ordinal"), numeric(1, 366, 3, Padding::Zero)),
+            'a' => (Some("weekday"), names(&WEEKDAYS, true)),
+            'A' => (Some("weekday"), names(&WEEKDAYS, false)),
+            'u' => (Some("weekday"), numeric(1, 7, 1, Padding::None)),
+            'w' => (Some("weekday"), numeric(0, 6, 1, Padding::None)),
+            'U' | 'W' => (Some("week"), numeric(0, 53, 2, Padding::Zero)),
+            'V' => (Some("week"), numeric(1, 53, 2, Padding::Zero)),
+            'H' => (Some("hour"), numeric(0, 23, 2, Padding::Zero)),
+            'k' => (Some("hour"), numeric(0, 23, 2, Padding::Space)),
+            'I' => (Some("hour"), numeric(1, 12, 2, Padding::Zero)),
+            'l' => (Some("hour"), numeric(1, 12, 2, Padding::Space)),
+            'M' => (Some("minute"), numeric(0, 59, 2, Padding::Zero)),
+            // Leap seconds are written as second 60
+            'S' => (Some("second"), numeric(0, 60, 2, Padding::Zero)),
+            'p' => (Some("am_pm"), chain(or(&["AM", "PM"]))),
+            'P' => (Some("am_pm"), chain(or(&["am", "pm"]))),
+            'Z' => (
+                Some("timezone"),
+                chain(one_or_more(within_range('A'..='Z'))),
+            ),
+            's' => (
+                Some("timestamp"),
+                zero_or_one(text("-")) + one_or_more(within_range('0'..='9')),
+            ),
+            _ => return Err(error(format!("unknown specifier `{}`", spec))),
+        };
+        literal_flush(&mut regex_string, &mut literal);
+        regex_string = regex_string
+            + match name {
+                Some(name) if captured.insert(name) => naming.capture(name, field),
+                _ => field,
+            };
+    }
+    literal_flush(&mut regex_string, &mut literal);
+    Ok(regex_string)
+}
+
+/// Add any literal text collected so far to the regex
+fn literal_flush(regex_string: &mut HumanRegex<SymbolChain>, literal: &mut String) {
+    if !literal.is_empty() {
+        let finished = std::mem::replace(regex_string, chain(text("")));
+        *regex_string = finished + text(std::mem::take(literal));
+    }
+}
+
+/// Matches an ISO 8601 calendar date, such as `2023-10-05`
+/// ```
+/// use human_regex::{beginning, end, patterns::datetime::iso8601_date};
+/// let regex_string = beginning() + iso8601_date() + end();
+/// assert!(regex_string.to_regex().is_match("2023-10-05"));
+/// assert!(!regex_string.to_regex().is_match("2023-10-32"));
+/// ```
+pub fn iso8601_date() -> HumanRegex<SymbolChain> {
+    iso8601_date_with(Naming::Unnamed)
+}
+
+/// Matches an ISO 8601 calendar date, capturing the `year`, `month` and `day`
+pub fn iso8601_date_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    from_strftime_with("%Y-%m-%d", naming).expect("the format is valid")
+}
+
+/// A time of day with optional seconds and fraction, followed by an offset from UTC. The offset
+/// is optional unless `offset_required` is set.
+fn time_of_day(
+    naming: Naming,
+    seconds_required: bool,
+    offset_required: bool,
+) -> HumanRegex<SymbolChain> {
+    let seconds = text(":")
+        + naming.capture("second", padded(0, 60, 2, Padding::Zero))
+        + zero_or_one(
+            within_set(&['.', ','])
+                + naming.capture("fraction", one_or_more(within_range('0'..='9'))),
+        );
+    let offset = naming.capture(
+        "offset",
+        or(&[
+            chain(within_set(&['Z', 'z'])),
+            within_set(&['+', '-'])
+                + padded(0, 23, 2, Padding::Zero)
+                + zero_or_one(zero_or_one(text(":")) + padded(0, 59, 2, Padding::Zero)),
+        ]),
+    );
+    naming.capture("hour", padded(0, 23, 2, Padding::Zero))
+        + text(":")
+        + naming.capture("minute", padded(0, 59, 2, Padding::Zero))
+        + if seconds_required {
+            seconds
+        } else {
+            chain(zero_or_one(seconds))
+        }
+        + if offset_required {
+            offset
+        } else {
+            chain(zero_or_one(offset))
+        }
+}
+
+/// Matches an ISO 8601 date and time, such as `2023-10-05T14:30`, with optional seconds,
+/// fractional seconds and offset from UTC
+/// ```
+/// use human_regex::{beginning, end, patterns::datetime::iso8601_datetime};
+/// let regex_string = beginning() + iso8601_datetime() + end();
+/// assert!(regex_string.to_regex().is_match("2023-10-05T14:30"));
+/// assert!(regex_string.to_regex().is_match("2023-10-05T14:30:15.250+02"));
+/// assert!(regex_string.to_regex().is_match("2023-10-05T14:30:15,5-05:30"));
+/// assert!(!regex_string.to_regex().is_match("2023-10-05T24:00"));
+/// ```
+pub fn iso8601_datetime() -> HumanRegex<SymbolChain> {
+    iso8601_datetime_with(Naming::Unnamed)
+}
+
+/// Matches an ISO 8601 date and time, capturing each field and the `offset`
+/// ```
+/// use human_regex::{patterns::datetime::iso8601_datetime_with, patterns::Naming};
+/// let caps = iso8601_datetime_with(Naming::Named).to_regex().captures("2023-10-05T14:30:15Z").unwrap();
+/// assert_eq!(&caps["second"], "15");
+/// assert_eq!(&caps["offset"], "Z");
+/// ```
+pub fn iso8601_datetime_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    iso8601_date_with(naming) + text("T") + time_of_day(naming, false, false)
+}
+
+/// Matches an RFC 3339 timestamp, such as `2023-10-05T14:30:15.25Z`, where the seconds and the
+/// offset are required
+/// ```
+/// use human_regex::{beginning, end, patterns::datetime::rfc3339};
+/// let regex_string = beginning() + rfc3339() + end();
+/// assert!(regex_string.to_regex().is_match("2023-10-05T14:30:15.25Z"));
+/// assert!(regex_string.to_regex().is_match("2023-10-05 14:30:15+02:00"));
+/// assert!(!regex_string.to_regex().is_match("2023-10-05T14:30Z"));
+/// ```
+pub fn rfc3339() -> HumanRegex<SymbolChain> {
+    rfc3339_with(Naming::Unnamed)
+}
+
+/// Matches an RFC 3339 timestamp, capturing each field and the `offset`
+pub fn rfc3339_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    iso8601_date_with(naming) + within_set(&['T', 't', ' ']) + time_of_day(naming, true, true)
+}
+
+/// Matches an RFC 2822 date as used in email headers, such as `Thu, 5 Oct 2023 14:30:15 +0200`,
+/// with an optional day of the week and optional seconds
+/// ```
+/// use human_regex::{beginning, end, patterns::datetime::rfc2822};
+/// let regex_string = beginning() + rfc2822() + end();
+/// assert!(regex_string.to_regex().is_match("Thu, 5 Oct 2023 14:30:15 +0200"));
+/// assert!(regex_string.to_regex().is_match("05 Oct 2023 14:30 GMT"));
+/// assert!(!regex_string.to_regex().is_match("Thu, 5 October 2023 14:30:15 +0200"));
+/// ```
+pub fn rfc2822() -> HumanRegex<SymbolChain> {
+    rfc2822_with(Naming::Unnamed)
+}
+
+/// Matches an RFC 2822 date, capturing each field and the `offset`
+pub fn rfc2822_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    let zone = or(&[
+        utc_offset(false),
+        chain(or(&[
+            "UT", "GMT", "EST", "EDT", "CST", "CDT", "MST", "MDT", "PST", "PDT", "Z",
+        ])),
+    ]);
+    zero_or_one(naming.capture("weekday", names(&WEEKDAYS, true)) + text(", "))
+        + naming.capture(
+            "day",
+            or(&[
+                padded(1, 31, 2, Padding::Zero),
+                padded(1, 9, 1, Padding::None),
+            ]),
+        )
+        + text(" ")
+        + naming.capture("month_name", names(&MONTHS, true))
+        + text(" ")
+        + naming.capture("year", padded(0, 9999, 4, Padding::Zero))
+        + text(" ")
+        + naming.capture("hour", padded(0, 23, 2, Padding::Zero))
+        + text(":")
+        + naming.capture("minute", padded(0, 59, 2, Padding::Zero))
+        + zero_or_one(text(":") + naming.capture("second", padded(0, 60, 2, Padding::Zero)))
+        + text(" ")
+        + naming.capture("offset", zone)
+}
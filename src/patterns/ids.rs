@@ -0,0 +1,479 @@
+//! Patterns for identifiers and account numbers, such as UUIDs, hashes, card numbers and IBANs
+//!
+//! Card numbers and IBANs end in check digits that no regex can verify, so [credit_card] and [iban]
+//! return a [Validated] pattern, which pairs the regex with a function that checks each match and
+//! compiles into a [VerifiedRegex].
+//! ```
+//! use human_regex::patterns::ids::{credit_card, Network};
+//! let cards = credit_card(Network::Visa).to_validated_regex();
+//! let found: Vec<&str> = cards
+//!     .find_iter("paid with 4111111111111111, not 4111111111111112")
+//!     .map(|m| m.as_str())
+//!     .collect();
+//! assert_eq!(found, vec!["4111111111111111"]);
+//! ```
+
+use super::chain;
+use crate::humanregex::*;
+use crate::{
+    between, exactly, hexdigit, number_in_range, or, text, within_range, within_set, word_boundary,
+    VerifiedRegex,
+};
+
+/// A pattern paired with a function that every match also has to pass
+#[derive(Debug)]
+pub struct Validated {
+    /// The pattern that finds candidates
+    pub pattern: HumanRegex<SymbolChain>,
+    /// The function that decides whether the text of a candidate is valid
+    pub validator: fn(&str) -> bool,
+}
+
+impl Validated {
+    /// Compile the pattern into a [VerifiedRegex] that only reports candidates that pass the
+    /// validator
+    pub fn to_validated_regex(&self) -> VerifiedRegex {
+        self.pattern
+            .to_verified_regex()
+            .with_validator(self.validator)
+    }
+}
+
+/// The letter case of hexadecimal digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LetterCase {
+    /// Only `a` to `f`
+    Lower,
+    /// Only `A` to `F`
+    Upper,
+    /// Either case, even mixed
+    #[default]
+    Either,
+}
+
+/// A hexadecimal digit in the given case
+fn hex(case: LetterCase) -> HumanRegex<SymbolChain> {
+    match case {
+        LetterCase::Lower => chain(within_set(&["0-9", "a-f"])),
+        LetterCase::Upper => chain(within_set(&["0-9", "A-F"])),
+        LetterCase::Either => chain(hexdigit()),
+    }
+}
+
+/// An ASCII decimal digit
+fn decimal_digit() -> HumanRegex<SymbolClass<Custom>> {
+    within_range('0'..='9')
+}
+
+/// Matches a UUID in its hyphenated form, such as `123e4567-e89b-12d3-a456-426614174000`
+/// ```
+/// use human_regex::{beginning, end, patterns::ids::{uuid, LetterCase}};
+/// let any = beginning() + uuid(None, LetterCase::Either) + end();
+/// assert!(any.to_regex().is_match("00000000-0000-0000-0000-000000000000"));
+///
+/// let v4 = beginning() + uuid(Some(4), LetterCase::Lower) + end();
+/// assert!(v4.to_regex().is_match("f47ac10b-58cc-4372-a567-0e02b2c3d479"));
+/// assert!(!v4.to_regex().is_match("F47AC10B-58CC-4372-A567-0E02B2C3D479"));
+/// assert!(!v4.to_regex().is_match("f47ac10b-58cc-1372-a567-0e02b2c3d479"));
+/// assert!(!v4.to_regex().is_match("f47ac10b-58cc-4372-c567-0e02b2c3d479"));
+/// ```
+///
+/// Without a version, any hexadecimal digits are matched. With one, the version digit has to be
+/// that version and the variant digit has to be `8`, `9`, `a` or `b`, as RFC 9562 lays out.
+///
+/// # Panics
+///
+/// Panics if the version is not between 1 and 8.
+pub fn uuid(version: Option<u8>, case: LetterCase) -> HumanRegex<SymbolChain> {
+    let digits = |count: u8| exactly(count, hex(case));
+    let (version_digits, variant_digits) = match version {
+        None => (chain(digits(4)), chain(digits(4))),
+        Some(version) => {
+            assert!(
+                (1..=8).contains(&version),
+                "there is no UUID version {}",
+                version
+            );
+            let variant = match case {
+                LetterCase::Lower => within_set(&["89ab"]),
+                LetterCase::Upper => within_set(&["89AB"]),
+                LetterCase::Either => within_set(&["89abAB"]),
+            };
+            (text(version.to_string()) + digits(3), variant + digits(3))
+        }
+    };
+    digits(8)
+        + text("-")
+        + digits(4)
+        + text("-")
+        + version_digits
+        + text("-")
+        + variant_digits
+        + text("-")
+        + digits(12)
+}
+
+/// Matches a ULID, 26 characters of Crockford's base 32 such as `01ARZ3NDEKTSV4RRFFQ69G5FAV`
+/// ```
+/// use human_regex::{beginning, end, patterns::ids::ulid};
+/// let regex_string = beginning() + ulid() + end();
+/// assert!(regex_string.to_regex().is_match("01ARZ3NDEKTSV4RRFFQ69G5FAV"));
+/// assert!(regex_string.to_regex().is_match("01arz3ndektsv4rrffq69g5fav"));
+/// assert!(!regex_string.to_regex().is_match("81ARZ3NDEKTSV4RRFFQ69G5FAV"));
+/// assert!(!regex_string.to_regex().is_match("01ARZ3NDEKTSV4RRFFQ69G5FAU"));
+/// ```
+///
+/// The first character is at most `7`, since a ULID holds 128 bits, and the letters `I`, `L`, `O`
+/// and `U` are never used. Either case is accepted.
+pub fn ulid() -> HumanRegex<SymbolChain> {
+    within_range('0'..='7')
+        + exactly(
+            25,
+            within_set(&[
+                "0-9", "A-H", "JK", "MN", "P-T", "V-Z", "a-h", "jk", "mn", "p-t", "v-z",
+            ]),
+        )
+}
+
+/// Matches a hash written as hexadecimal digits, such as an MD5 digest for 128 bits or a SHA-256
+/// digest for 256
+/// ```
+/// use human_regex::{patterns::ids::hex_hash, word_boundary};
+/// let sha1 = word_boundary() + hex_hash(160) + word_boundary();
+/// let commit = "commit 2fd4e1c67a2d28fced849ee1bb76e7391b93eb12 (HEAD)";
+/// assert!(sha1.to_regex().is_match(commit));
+///
+/// let md5 = word_boundary() + hex_hash(128) + word_boundary();
+/// assert!(!md5.to_regex().is_match(commit));
+/// ```
+///
+/// Hashes are often found inside longer runs of hexadecimal digits, so surround the pattern with
+/// word boundaries as above to match only hashes of exactly this length.
+///
+/// # Panics
+///
+/// Panics unless the number of bits is a multiple of four between 4 and 1020.
+pub fn hex_hash(bits: u16) -> HumanRegex<SymbolChain> {
+    assert!(
+        bits % 4 == 0 && (4..=1020).contains(&bits),
+        "a hash of {} bits cannot be written in hexadecimal digits",
+        bits
+    );
+    chain(exactly((bits / 4) as u8, hexdigit()))
+}
+
+/// A payment card network, which decides the prefixes and lengths of its card numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    /// Numbers starting with 4, with 13, 16 or 19 digits
+    Visa,
+    /// Numbers starting with 51 to 55 or 2221 to 2720, with 16 digits
+    Mastercard,
+    /// Numbers starting with 34 or 37, with 15 digits
+    AmericanExpress,
+    /// Numbers starting with 6011, 622126 to 622925, 644 to 649 or 65, with 16 to 19 digits
+    Discover,
+    /// Numbers starting with 300 to 305, 3095, 36, 38 or 39, with 14 to 19 digits
+    DinersClub,
+    /// Numbers starting with 3528 to 3589, with 16 to 19 digits
+    Jcb,
+    /// Numbers starting with 62, with 16 to 19 digits
+    UnionPay,
+    /// The numbers of any of the other networks
+    Any,
+}
+
+impl Network {
+    /// The networks that [Network::Any] stands for
+    const ALL: [Network; 7] = [
+        Network::Visa,
+        Network::Mastercard,
+        Network::AmericanExpress,
+        Network::Discover,
+        Network::DinersClub,
+        Network::Jcb,
+        Network::UnionPay,
+    ];
+
+    /// The ranges of prefixes that the network issues, and the lengths of its numbers, longest first
+    fn rules(self) -> (&'static [(i64, i64)], &'static [u8]) {
+        match self {
+            Network::Visa => (&[(4, 4)], &[19, 16, 13]),
+            Network::Mastercard => (&[(51, 55), (2221, 2720)], &[16]),
+            Network::AmericanExpress => (&[(34, 34), (37, 37)], &[15]),
+            Network::Discover => (
+                &[(6011, 6011), (622126, 622925), (644, 649), (65, 65)],
+                &[19, 18, 17, 16],
+            ),
+            Network::DinersClub => (
+                &[(300, 305), (3095, 3095), (36, 36), (38, 39)],
+                &[19, 18, 17, 16, 15, 14],
+            ),
+            Network::Jcb => (&[(3528, 3589)], &[19, 18, 17, 16]),
+            Network::UnionPay => (&[(62, 62)], &[19, 18, 17, 16]),
+            Network::Any => (&[], &[]),
+        }
+    }
+
+    /// Matches the card numbers of a single network
+    fn pattern(self) -> HumanRegex<SymbolChain> {
+        let (prefixes, lengths) = self.rules();
+        let numbers: Vec<HumanRegex<SymbolChain>> = prefixes
+            .iter()
+            .map(|&(lo, hi)| {
+                let prefix = lo.to_string().len() as u8;
+                let shortest = lengths[lengths.len() - 1] - prefix;
+                let longest = lengths[0] - prefix;
+                // Visa's lengths are not consecutive, so they are listed one by one
+                let digits = if lengths.len() as u8 == longest - shortest + 1 {
+                    chain(between(shortest, longest, decimal_digit()))
+                } else {
+                    or(&lengths
+                        .iter()
+                        .map(|length| exactly(length - prefix, decimal_digit()))
+                        .collect::<Vec<_>>())
+                };
+                number_in_range(lo..=hi) + digits
+            })
+            .collect();
+        or(&numbers)
+    }
+}
+
+/// Whether a number passes the Luhn check that card numbers use, ignoring spaces and hyphens
+/// ```
+/// use human_regex::patterns::ids::luhn;
+/// assert!(luhn("4111 1111 1111 1111"));
+/// assert!(luhn("79927398713"));
+/// assert!(!luhn("79927398710"));
+/// assert!(!luhn("7992739871x"));
+/// ```
+pub fn luhn(number: &str) -> bool {
+    let mut sum = 0;
+    let mut count = 0;
+    for c in number.chars().rev().filter(|c| !matches!(c, ' ' | '-')) {
+        let Some(digit) = c.to_digit(10) else {
+            return false;
+        };
+        // Every second digit from the right is doubled, and the digits of the product summed
+        sum += match count % 2 {
+            0 => digit,
+            _ if digit > 4 => digit * 2 - 9,
+            _ => digit * 2,
+        };
+        count += 1;
+    }
+    count > 0 && sum % 10 == 0
+}
+
+/// Matches a payment card number of the given network, written without spaces, and validates its
+/// Luhn check digit
+///
+/// The number has to start and end at word boundaries, so that digits from a longer number are
+/// not taken for a card number.
+/// ```
+/// use human_regex::patterns::ids::{credit_card, Network};
+/// let amex = credit_card(Network::AmericanExpress);
+/// assert!(amex.to_validated_regex().is_match("378282246310005"));
+/// assert!(!amex.to_validated_regex().is_match("378282246310006"));
+///
+/// let any = credit_card(Network::Any).to_validated_regex();
+/// assert!(any.is_match("5555555555554444"));
+/// assert!(any.is_match("3530111333300000"));
+/// assert!(!any.is_match("1234567812345670"));
+/// ```
+pub fn credit_card(network: Network) -> Validated {
+    let pattern = match network {
+        Network::Any => or(&Network::ALL.map(Network::pattern)),
+        _ => network.pattern(),
+    };
+    Validated {
+        pattern: word_boundary() + pattern + word_boundary(),
+        validator: luhn,
+    }
+}
+
+/// The length of the IBANs of each country that uses them, as published in the IBAN registry
+const IBAN_LENGTHS: &[(&str, u8)] = &[
+    ("AD", 24),
+    ("AE", 23),
+    ("AL", 28),
+    ("AT", 20),
+    ("AZ", 28),
+    ("BA", 20),
+    ("BE", 16),
+    ("BG", 22),
+    ("BH", 22),
+    ("BI", 27),
+    ("BR", 29),
+    ("BY", 28),
+    ("CH", 21),
+    ("CR", 22),
+    ("CY", 28),
+    ("CZ", 24),
+    ("DE", 22),
+    ("DJ", 27),
+    ("DK", 18),
+    ("DO", 28),
+    ("EE", 20),
+    ("EG", 29),
+    ("ES", 24),
+    ("FI", 18),
+    ("FK", 18),
+    ("FO", 18),
+    ("FR", 27),
+    ("GB", 22),
+    ("GE", 22),
+    ("GI", 23),
+    ("GL", 18),
+    ("GR", 27),
+    ("GT", 28),
+    ("HR", 21),
+    ("HU", 28),
+    ("IE", 22),
+    ("IL", 23),
+    ("IQ", 23),
+    ("IS", 26),
+    ("IT", 27),
+    ("JO", 30),
+    ("KW", 30),
+    ("KZ", 20),
+    ("LB", 28),
+    ("LC", 32),
+    ("LI", 21),
+    ("LT", 20),
+    ("LU", 20),
+    ("LV", 21),
+    ("LY", 25),
+    ("MC", 27),
+    ("MD", 24),
+    ("ME", 22),
+    ("MK", 19),
+    ("MN", 20),
+    ("MR", 27),
+    ("MT", 31),
+    ("MU", 30),
+    ("NI", 28),
+    ("NL", 18),
+    ("NO", 15),
+    ("OM", 23),
+    ("PK", 24),
+    ("PL", 28),
+    ("PS", 29),
+    ("PT", 25),
+    ("QA", 29),
+    ("RO", 24),
+    ("RS", 22),
+    ("RU", 33),
+    ("SA", 24),
+    ("SC", 31),
+    ("SD", 18),
+    ("SE", 24),
+    ("SI", 19),
+    ("SK", 24),
+    ("SM", 27),
+    ("SO", 23),
+    ("ST", 25),
+    ("SV", 28),
+    ("TL", 23),
+    ("TN", 24),
+    ("TR", 26),
+    ("UA", 29),
+    ("VA", 22),
+    ("VG", 24),
+    ("XK", 20),
+    ("YE", 30),
+];
+
+/// Whether an IBAN's check digits are correct, ignoring spaces
+/// ```
+/// use human_regex::patterns::ids::iban_checksum;
+/// assert!(iban_checksum("GB82 WEST 1234 5698 7654 32"));
+/// assert!(!iban_checksum("GB82 WEST 1234 5698 7654 33"));
+/// ```
+pub fn iban_checksum(iban: &str) -> bool {
+    let compact: Vec<char> = iban.chars().filter(|c| *c != ' ').collect();
+    if compact.len() < 5 {
+        return false;
+    }
+    // The country code and check digits move to the end, and letters count as 10 to 35
+    let mut remainder = 0;
+    for c in compact[4..].iter().chain(&compact[..4]) {
+        let Some(value) = c.to_digit(36) else {
+            return false;
+        };
+        remainder = if value < 10 {
+            (remainder * 10 + value) % 97
+        } else {
+            (remainder * 100 + value) % 97
+        };
+    }
+    remainder == 1
+}
+
+/// Matches the IBANs of the countries with the same length, in the electronic form without spaces
+/// or the printed form with a space after every four characters
+fn iban_of_length(countries: &[&str], length: u8) -> HumanRegex<SymbolChain> {
+    let character = || within_set(&["0-9", "A-Z"]);
+    let start = || or(countries) + exactly(2, decimal_digit());
+    let (groups, rest) = ((length - 4) / 4, (length - 4) % 4);
+    let printed = start()
+        + exactly(groups, text(" ") + exactly(4, character()))
+        + if rest > 0 {
+            chain(text(" ") + exactly(rest, character()))
+        } else {
+            chain(text(""))
+        };
+    or(&[printed, start() + exactly(length - 4, character())])
+}
+
+/// Matches an IBAN of a country, or of any country for [None], and validates its check digits
+/// ```
+/// use human_regex::patterns::ids::iban;
+/// let german = iban(Some("DE")).to_validated_regex();
+/// assert!(german.is_match("DE89370400440532013000"));
+/// assert!(german.is_match("DE89 3704 0044 0532 0130 00"));
+/// assert!(!german.is_match("DE88370400440532013000"));
+/// assert!(!german.is_match("GB82WEST12345698765432"));
+///
+/// let any = iban(None).to_validated_regex();
+/// assert_eq!(any.find("IBAN: GB82WEST12345698765432.").unwrap().as_str(), "GB82WEST12345698765432");
+/// ```
+///
+/// The IBAN has to start and end at word boundaries. The country-specific structure of the account
+/// number is not checked, only its length and characters.
+///
+/// # Panics
+///
+/// Panics if the country does not use IBANs.
+pub fn iban(country: Option<&str>) -> Validated {
+    let pattern = match country {
+        Some(country) => {
+            let &(code, length) = IBAN_LENGTHS
+                .iter()
+                .find(|(code, _)| *code == country)
+                .unwrap_or_else(|| panic!("there are no IBANs for the country `{}`", country));
+            iban_of_length(&[code], length)
+        }
+        None => {
+            let mut lengths: Vec<u8> = IBAN_LENGTHS.iter().map(|&(_, length)| length).collect();
+            lengths.sort_unstable_by(|a, b| b.cmp(a));
+            lengths.dedup();
+            or(&lengths
+                .into_iter()
+                .map(|length| {
+                    let countries: Vec<&str> = IBAN_LENGTHS
+                        .iter()
+                        .filter(|&&(_, other)| other == length)
+                        .map(|&(code, _)| code)
+                        .collect();
+                    iban_of_length(&countries, length)
+                })
+                .collect::<Vec<_>>())
+        }
+    };
+    Validated {
+        pattern: word_boundary() + pattern + word_boundary(),
+        validator: iban_checksum,
+    }
+}
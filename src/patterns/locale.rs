@@ -0,0 +1,278 @@
+//! Patterns for phone numbers and postal codes, which are written differently in every country
+//!
+//! The rules for each [Country] are kept in one table, so supporting another country only means
+//! adding a variant and its rows.
+//! ```
+//! use human_regex::{beginning, end, patterns::locale::{phone_number, postal_code, Country}};
+//! let country: Country = "GB".parse().unwrap();
+//! assert!((beginning() + phone_number(country) + end()).to_regex().is_match("+44 (0)20 7946 0018"));
+//! assert!((beginning() + postal_code(country) + end()).to_regex().is_match("SW1A 1AA"));
+//! ```
+
+use super::{chain, Naming};
+use crate::humanregex::*;
+use crate::{
+    between, case_insensitive, escape_all, exactly, or, text, whitespace, within_range, within_set,
+    zero_or_more, zero_or_one,
+};
+
+/// A country with its own conventions for phone numbers and postal codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Country {
+    /// The United States, or `US`
+    UnitedStates,
+    /// Canada, or `CA`
+    Canada,
+    /// The United Kingdom, or `GB`
+    UnitedKingdom,
+    /// Germany, or `DE`
+    Germany,
+    /// France, or `FR`
+    France,
+    /// Italy, or `IT`
+    Italy,
+    /// Spain, or `ES`
+    Spain,
+    /// The Netherlands, or `NL`
+    Netherlands,
+    /// Australia, or `AU`
+    Australia,
+    /// Japan, or `JP`
+    Japan,
+    /// India, or `IN`
+    India,
+    /// Brazil, or `BR`
+    Brazil,
+    /// China, or `CN`
+    China,
+    /// Mexico, or `MX`
+    Mexico,
+}
+
+impl Country {
+    /// The country calling code, the trunk prefix dialled before national numbers if there is
+    /// one, the digits a national significant number can start with, and the fewest and most
+    /// digits such a number has
+    fn phone_rules(self) -> (&'static str, Option<&'static str>, &'static str, u8, u8) {
+        match self {
+            Country::UnitedStates | Country::Canada => ("1", Some("1"), "2-9", 10, 10),
+            Country::UnitedKingdom => ("44", Some("0"), "1-35-9", 9, 10),
+            Country::Germany => ("49", Some("0"), "1-9", 6, 13),
+            Country::France => ("33", Some("0"), "1-9", 9, 9),
+            Country::Italy => ("39", None, "03", 6, 11),
+            Country::Spain => ("34", None, "6-9", 9, 9),
+            Country::Netherlands => ("31", Some("0"), "1-9", 9, 9),
+            Country::Australia => ("61", Some("0"), "2-478", 9, 9),
+            Country::Japan => ("81", Some("0"), "1-9", 9, 10),
+            Country::India => ("91", Some("0"), "1-9", 10, 10),
+            Country::Brazil => ("55", Some("0"), "1-9", 10, 11),
+            Country::China => ("86", Some("0"), "1-9", 9, 11),
+            Country::Mexico => ("52", None, "1-9", 10, 10),
+        }
+    }
+}
+
+impl std::str::FromStr for Country {
+    type Err = String;
+
+    /// Parses an ISO 3166-1 alpha-2 code, such as `US` or `gb`
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        match code.to_ascii_uppercase().as_str() {
+            "US" => Ok(Country::UnitedStates),
+            "CA" => Ok(Country::Canada),
+            "GB" | "UK" => Ok(Country::UnitedKingdom),
+            "DE" => Ok(Country::Germany),
+            "FR" => Ok(Country::France),
+            "IT" => Ok(Country::Italy),
+            "ES" => Ok(Country::Spain),
+            "NL" => Ok(Country::Netherlands),
+            "AU" => Ok(Country::Australia),
+            "JP" => Ok(Country::Japan),
+            "IN" => Ok(Country::India),
+            "BR" => Ok(Country::Brazil),
+            "CN" => Ok(Country::China),
+            "MX" => Ok(Country::Mexico),
+            _ => Err(format!("unknown country code `{}`", code)),
+        }
+    }
+}
+
+/// An ASCII decimal digit
+fn digit() -> HumanRegex<SymbolClass<Custom>> {
+    within_range('0'..='9')
+}
+
+/// An optional space, dot or hyphen between the digits of a phone number
+fn separator() -> HumanRegex<Quantifier> {
+    zero_or_one(within_set(&[" ", r"\.", r"\-"]))
+}
+
+/// Between `fewest` and `most` digits, where each digit after the first may follow a separator
+fn digits(first: HumanRegex<SymbolChain>, fewest: u8, most: u8) -> HumanRegex<SymbolChain> {
+    first + between(fewest - 1, most - 1, separator() + digit())
+}
+
+/// Matches a phone number of the given country, in international form such as `+44 20 7946 0018`
+/// or `0044 20 7946 0018`, or in national form such as `020 7946 0018`
+/// ```
+/// use human_regex::{beginning, end, patterns::locale::{phone_number, Country}};
+/// let regex_string = beginning() + phone_number(Country::UnitedStates) + end();
+/// for number in ["+1 555 234 5678", "(555) 234-5678", "1-800-555-0199", "555.234.5678 ext. 42", "+15552345678"] {
+///     assert!(regex_string.to_regex().is_match(number));
+/// }
+/// assert!(!regex_string.to_regex().is_match("555-234-567"));
+/// assert!(!regex_string.to_regex().is_match("+44 20 7946 0018"));
+///
+/// let regex_string = beginning() + phone_number(Country::Germany) + end();
+/// assert!(regex_string.to_regex().is_match("+49 30 901820"));
+/// assert!(regex_string.to_regex().is_match("(030) 901820-0"));
+/// ```
+///
+/// Digits may be grouped with spaces, dots or hyphens, the area code may be written in
+/// parentheses, and an extension may follow `ext`, `x` or `#`. Only the number of digits and the
+/// digits a number can start with are checked, not whether the area code is in use.
+pub fn phone_number(country: Country) -> HumanRegex<SymbolChain> {
+    phone_number_with(country, Naming::Unnamed)
+}
+
+/// Matches a phone number of the given country, capturing the `country_code` of an international
+/// number, the rest of the `number` as written, and the `extension`
+/// ```
+/// use human_regex::{patterns::locale::{phone_number_with, Country}, patterns::Naming};
+/// let regex_string = phone_number_with(Country::UnitedKingdom, Naming::Named);
+/// let caps = regex_string.to_regex().captures("Call +44 (0)20 7946 0018 x204 today").unwrap();
+/// assert_eq!(&caps["country_code"], "44");
+/// assert_eq!(&caps["number"], "(0)20 7946 0018");
+/// assert_eq!(&caps["extension"], "204");
+/// ```
+pub fn phone_number_with(country: Country, naming: Naming) -> HumanRegex<SymbolChain> {
+    let (calling_code, trunk, first, fewest, most) = country.phone_rules();
+    let first = || chain(within_set(&[first]));
+    let trunk = || match trunk {
+        Some(trunk) => chain(zero_or_one(or(&[
+            text("(") + text(trunk) + text(")"),
+            chain(text(trunk)),
+        ]))),
+        None => chain(text("")),
+    };
+    // The area code in parentheses can have up to four digits, and never the whole number
+    let mut forms: Vec<HumanRegex<SymbolChain>> = (1..=4)
+        .filter(|&area| area < fewest)
+        .map(|area| {
+            chain(text("("))
+                + trunk()
+                + first()
+                + exactly(area - 1, digit())
+                + text(")")
+                + separator()
+                + digits(chain(digit()), fewest - area, most - area)
+        })
+        .collect();
+    forms.push(trunk() + separator() + digits(first(), fewest, most));
+    let international = or(&["\\+", "00"]) + naming.capture("country_code", text(calling_code));
+    let extension = zero_or_more(whitespace())
+        + case_insensitive(or(&escape_all(&["ext.", "ext", "x", "#"])))
+        + zero_or_more(whitespace())
+        + naming.capture("extension", between(1, 6, digit()));
+    zero_or_one(international + separator())
+        + naming.capture("number", or(&forms))
+        + zero_or_one(extension)
+}
+
+/// Matches a postal code of the given country, such as `90210-1234`, `K1A 0B1` or `75008`
+/// ```
+/// use human_regex::{beginning, end, patterns::locale::{postal_code, Country}};
+/// let matches = |country, code| (beginning() + postal_code(country) + end()).to_regex().is_match(code);
+/// assert!(matches(Country::UnitedStates, "90210-1234"));
+/// assert!(matches(Country::Canada, "K1A 0B1"));
+/// assert!(!matches(Country::Canada, "D1A 0B1"));
+/// assert!(matches(Country::UnitedKingdom, "EC1A 1BB"));
+/// assert!(matches(Country::Netherlands, "1012 AB"));
+/// assert!(matches(Country::Japan, "100-0001"));
+/// assert!(matches(Country::France, "75008"));
+/// assert!(!matches(Country::France, "99000"));
+/// ```
+///
+/// Letters have to be in upper case, so wrap the pattern in [case_insensitive](crate::case_insensitive)
+/// to accept what people type.
+pub fn postal_code(country: Country) -> HumanRegex<SymbolChain> {
+    postal_code_with(country, Naming::Unnamed)
+}
+
+/// Matches a postal code of the given country, capturing its parts where it has them, and the
+/// whole `code` otherwise:
+/// - the `zip` and `plus4` of a US ZIP code,
+/// - the forward sortation area `fsa` and the local delivery unit `ldu` of a Canadian code,
+/// - and the `outward` and `inward` codes of a UK postcode.
+/// ```
+/// use human_regex::{patterns::locale::{postal_code_with, Country}, patterns::Naming};
+/// let regex_string = postal_code_with(Country::UnitedKingdom, Naming::Named);
+/// let caps = regex_string.to_regex().captures("London SW1A 1AA").unwrap();
+/// assert_eq!(&caps["outward"], "SW1A");
+/// assert_eq!(&caps["inward"], "1AA");
+///
+/// let regex_string = postal_code_with(Country::Germany, Naming::Prefixed("shipping"));
+/// assert_eq!(&regex_string.to_regex().captures("10117 Berlin").unwrap()["shipping_code"], "10117");
+/// ```
+pub fn postal_code_with(country: Country, naming: Naming) -> HumanRegex<SymbolChain> {
+    let code = |pattern: HumanRegex<SymbolChain>| naming.capture("code", pattern);
+    let digits = |count: u8| chain(exactly(count, digit()));
+    match country {
+        Country::UnitedStates => {
+            naming.capture("zip", digits(5))
+                + zero_or_one(text("-") + naming.capture("plus4", digits(4)))
+        }
+        Country::Canada => {
+            // D, F, I, O, Q and U are never used, nor W and Z as the first letter
+            let letter = || within_set(&["ABCEGHJ-NPRSTV-Z"]);
+            naming.capture(
+                "fsa",
+                within_set(&["ABCEGHJ-NPRSTVXY"]) + digit() + letter(),
+            ) + zero_or_one(text(" "))
+                + naming.capture("ldu", digit() + letter() + digit())
+        }
+        Country::UnitedKingdom => {
+            let area = || within_set(&["A-PR-UWYZ"]);
+            let outward = or(&[
+                area() + within_set(&["A-HK-Y"]) + digit() + within_set(&["ABEHMNPRVWXY"]),
+                area() + within_set(&["A-HK-Y"]) + digit() + zero_or_one(digit()),
+                area() + digit() + within_set(&["A-HJKPSTUW"]),
+                area() + digit() + zero_or_one(digit()),
+            ]);
+            naming.capture("outward", outward)
+                + zero_or_one(text(" "))
+                + naming.capture(
+                    "inward",
+                    digit() + exactly(2, within_set(&["ABD-HJLNP-UW-Z"])),
+                )
+        }
+        Country::France => code(
+            or(&[
+                text("0") + within_range('1'..='9'),
+                within_range('1'..='8') + digit(),
+                text("9") + within_range('0'..='8'),
+            ]) + digits(3),
+        ),
+        Country::Spain => code(
+            or(&[
+                text("0") + within_range('1'..='9'),
+                within_range('1'..='4') + digit(),
+                text("5") + within_range('0'..='2'),
+            ]) + digits(3),
+        ),
+        Country::Netherlands => code(
+            within_range('1'..='9')
+                + digits(3)
+                + zero_or_one(text(" "))
+                + exactly(2, within_range('A'..='Z')),
+        ),
+        Country::Japan => code(digits(3) + text("-") + digits(4)),
+        Country::India => {
+            code(within_range('1'..='9') + digits(2) + zero_or_one(text(" ")) + digits(3))
+        }
+        Country::Brazil => code(digits(5) + zero_or_one(text("-")) + digits(3)),
+        Country::Australia => code(digits(4)),
+        Country::China => code(digits(6)),
+        Country::Germany | Country::Italy | Country::Mexico => code(digits(5)),
+    }
+}
@@ -0,0 +1,395 @@
+//! Patterns for the lines of common log formats
+//!
+//! With [Naming::Named] each builder captures the fields of a line under the names its `_with`
+//! variant lists. Timestamps are captured whole as `time`, and match only valid dates and times.
+//! ```
+//! use human_regex::{beginning, end, patterns::logs::combined_log_with, patterns::Naming};
+//! let line = r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326 "http://www.example.com/start.html" "Mozilla/4.08""#;
+//! let caps = (beginning() + combined_log_with(Naming::Named) + end()).to_regex().captures(line).unwrap();
+//! assert_eq!(&caps["user"], "frank");
+//! assert_eq!(&caps["path"], "/apache_pb.gif");
+//! assert_eq!(&caps["status"], "200");
+//! assert_eq!(&caps["user_agent"], "Mozilla/4.08");
+//! ```
+
+use super::datetime::{from_strftime, rfc3339, FormatError};
+use super::{chain, Naming};
+use crate::humanregex::*;
+use crate::{
+    any, between, exactly, non_whitespace, number_in_range, one_or_more, or, text, whitespace,
+    within_range, within_set, without_set, zero_or_more, zero_or_one,
+};
+use std::collections::HashSet;
+
+/// The format of nginx's predefined `combined` log format, which it uses when no other is given
+pub const NGINX_COMBINED: &str = r#"$remote_addr - $remote_user [$time_local] "$request" $status $body_bytes_sent "$http_referer" "$http_user_agent""#;
+
+/// The timestamp of the Common Log Format and of nginx's `$time_local`, such as
+/// `10/Oct/2000:13:55:36 -0700`
+const CLF_TIME: &str = "%d/%b/%Y:%H:%M:%S %z";
+
+/// An ASCII decimal digit
+fn decimal_digit() -> HumanRegex<SymbolClass<Custom>> {
+    within_range('0'..='9')
+}
+
+/// A field of characters other than whitespace, such as a host name or `-` for a missing value
+fn token() -> HumanRegex<SymbolChain> {
+    chain(one_or_more(non_whitespace()))
+}
+
+/// The text between double quotes, where a `\` escapes the character after it
+fn quoted_text() -> HumanRegex<SymbolChain> {
+    chain(zero_or_more(or(&[
+        chain(without_set(&["\"", r"\\"])),
+        text(r"\") + any(),
+    ])))
+}
+
+/// Matches a line in the Common Log Format written by web servers, such as
+/// `127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326`
+/// ```
+/// use human_regex::{beginning, end, patterns::logs::common_log};
+/// let regex_string = beginning() + common_log() + end();
+/// assert!(regex_string.to_regex().is_match(r#"::1 - - [10/Oct/2000:13:55:36 +0000] "-" 408 -"#));
+/// assert!(!regex_string.to_regex().is_match(r#"::1 - - [10/Oct/2000:25:55:36 +0000] "-" 408 -"#));
+/// ```
+pub fn common_log() -> HumanRegex<SymbolChain> {
+    common_log_with(Naming::Unnamed)
+}
+
+/// Matches a line in the Common Log Format, capturing the `host`, `ident`, `user`, `time`,
+/// `request`, `status` and `bytes`, along with the `method`, `path` and `protocol` when the request
+/// is a well-formed HTTP request line
+pub fn common_log_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    let request_line = naming.capture("method", one_or_more(within_range('A'..='Z')))
+        + text(" ")
+        + naming.capture("path", one_or_more(without_set(&[r"\s", "\""])))
+        + text(" ")
+        + naming.capture(
+            "protocol",
+            text("HTTP/") + one_or_more(within_set(&["0-9", r"\."])),
+        );
+    naming.capture("host", token())
+        + text(" ")
+        + naming.capture("ident", token())
+        + text(" ")
+        + naming.capture("user", token())
+        + text(" [")
+        + naming.capture("time", from_strftime(CLF_TIME).unwrap())
+        + text("] \"")
+        + naming.capture("request", or(&[request_line, quoted_text()]))
+        + text("\" ")
+        + naming.capture("status", exactly(3, decimal_digit()))
+        + text(" ")
+        + naming.capture(
+            "bytes",
+            or(&[chain(one_or_more(decimal_digit())), chain(text("-"))]),
+        )
+}
+
+/// Matches a line in the Combined Log Format, which is the Common Log Format followed by the
+/// quoted referrer and user agent
+/// ```
+/// use human_regex::{beginning, end, patterns::logs::combined_log};
+/// let regex_string = beginning() + combined_log() + end();
+/// let line = r#"10.0.0.7 - - [05/Oct/2023:14:30:15 +0200] "GET / HTTP/2.0" 200 512 "-" "curl/8.4.0""#;
+/// assert!(regex_string.to_regex().is_match(line));
+/// ```
+pub fn combined_log() -> HumanRegex<SymbolChain> {
+    combined_log_with(Naming::Unnamed)
+}
+
+/// Matches a line in the Combined Log Format, capturing the fields of [common_log_with] along with
+/// the `referer` and `user_agent`
+pub fn combined_log_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    common_log_with(naming)
+        + text(" \"")
+        + naming.capture("referer", quoted_text())
+        + text("\" \"")
+        + naming.capture("user_agent", quoted_text())
+        + text("\"")
+}
+
+/// Matches a line written with nginx's predefined `combined` log format, [NGINX_COMBINED]
+/// ```
+/// use human_regex::{beginning, end, patterns::logs::nginx_default_with, patterns::Naming};
+/// let regex_string = beginning() + nginx_default_with(Naming::Named) + end();
+/// let line = r#"203.0.113.9 - - [05/Oct/2023:14:30:15 +0000] "POST /api HTTP/1.1" 201 17 "-" "Go-http-client/1.1""#;
+/// let caps = regex_string.to_regex().captures(line).unwrap();
+/// assert_eq!(&caps["remote_addr"], "203.0.113.9");
+/// assert_eq!(&caps["request"], "POST /api HTTP/1.1");
+/// assert_eq!(&caps["body_bytes_sent"], "17");
+/// ```
+pub fn nginx_default() -> HumanRegex<SymbolChain> {
+    nginx_default_with(Naming::Unnamed)
+}
+
+/// Matches a line written with nginx's predefined `combined` log format, capturing each variable
+/// under its own name
+pub fn nginx_default_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    from_nginx_log_format_with(NGINX_COMBINED, naming).unwrap()
+}
+
+/// Converts the format of an nginx `log_format` directive into a pattern for the lines it writes
+/// ```
+/// use human_regex::{beginning, end, patterns::logs::from_nginx_log_format};
+/// let format = "$remote_addr [$time_iso8601] $request_time ${upstream_addr}";
+/// let regex_string = beginning() + from_nginx_log_format(format).unwrap() + end();
+/// assert!(regex_string.to_regex().is_match("10.0.0.1 [2023-10-05T14:30:15+02:00] 0.004 10.0.1.5:8080"));
+/// assert!(!regex_string.to_regex().is_match("10.0.0.1 [2023-10-05T14:30:15+02:00] fast 10.0.1.5:8080"));
+///
+/// assert_eq!(from_nginx_log_format("${status").unwrap_err().to_string(), "column 1: `${` is never closed");
+/// ```
+///
+/// Variables with a known form, such as `$status`, `$time_local` or `$request_time`, only match
+/// values of that form. Any other variable matches everything up to the character that follows it
+/// in the format, or the rest of the line if nothing does.
+pub fn from_nginx_log_format(format: &str) -> Result<HumanRegex<SymbolChain>, FormatError> {
+    from_nginx_log_format_with(format, Naming::Unnamed)
+}
+
+/// Converts the format of an nginx `log_format` directive into a pattern, capturing each variable
+/// under its own name, such as `remote_addr`. A variable that appears more than once is only
+/// captured the first time, and the numbered variables of a regex, such as `$1`, are not captured
+/// since a capture group name cannot start with a digit.
+/// ```
+/// use human_regex::{patterns::logs::from_nginx_log_format_with, patterns::Naming};
+/// let format = r#"$host "$request" $status $upstream_response_time"#;
+/// let regex_string = from_nginx_log_format_with(format, Naming::Prefixed("nginx")).unwrap();
+/// let caps = regex_string.to_regex().captures(r#"example.com "GET / HTTP/1.1" 502 0.001, 0.002"#).unwrap();
+/// assert_eq!(&caps["nginx_status"], "502");
+/// assert_eq!(&caps["nginx_upstream_response_time"], "0.001, 0.002");
+///
+/// let regex_string = from_nginx_log_format_with("$1 $uri", Naming::Named).unwrap();
+/// assert_eq!(&regex_string.to_regex().captures("v2 /api").unwrap()["uri"], "/api");
+/// ```
+pub fn from_nginx_log_format_with(
+    format: &str,
+    naming: Naming,
+) -> Result<HumanRegex<SymbolChain>, FormatError> {
+    // Split the format into literal text and variables first, as a variable's pattern can depend
+    // on the text after it
+    let mut pieces: Vec<(bool, String)> = Vec::new();
+    let mut chars = format.chars().enumerate().peekable();
+    while let Some((column, c)) = chars.next() {
+        if c != '$' {
+            match pieces.last_mut() {
+                Some((false, literal)) => literal.push(c),
+                _ => pieces.push((false, c.to_string())),
+            }
+            continue;
+        }
+        let error = |message: &str| FormatError {
+            column: column + 1,
+            message: message.to_string(),
+        };
+        let braced = chars.next_if(|&(_, c)| c == '{').is_some();
+        let mut name = String::new();
+        while let Some((_, c)) = chars.next_if(|&(_, c)| c.is_ascii_alphanumeric() || c == '_') {
+            name.push(c);
+        }
+        if braced && chars.next_if(|&(_, c)| c == '}').is_none() {
+            return Err(error("`${` is never closed"));
+        }
+        if name.is_empty() {
+            return Err(error("expected a variable name after `$`"));
+        }
+        pieces.push((true, name));
+    }
+
+    let mut captured = HashSet::new();
+    let mut regex_string = chain(text(""));
+    for (index, (variable, piece)) in pieces.iter().enumerate() {
+        if !variable {
+            regex_string = regex_string + text(piece);
+            continue;
+        }
+        let next = pieces
+            .get(index + 1)
+            .and_then(|(variable, piece)| (!variable).then(|| piece.chars().next()).flatten());
+        let value = nginx_variable(piece, next);
+        let numbered = piece.starts_with(|c: char| c.is_ascii_digit());
+        regex_string = if !numbered && captured.insert(piece.as_str()) {
+            regex_string + naming.capture(piece, value)
+        } else {
+            regex_string + value
+        };
+    }
+    Ok(regex_string)
+}
+
+/// The values an nginx variable can have, given the character that follows it in the format
+fn nginx_variable(name: &str, next: Option<char>) -> HumanRegex<SymbolChain> {
+    let number = || one_or_more(decimal_digit());
+    let seconds = |decimals: u8| number() + text(".") + exactly(decimals, decimal_digit());
+    match name {
+        "time_local" => from_strftime(CLF_TIME).unwrap(),
+        "time_iso8601" => from_strftime("%Y-%m-%dT%H:%M:%S%:z").unwrap(),
+        "msec" | "request_time" => seconds(3),
+        "status" => chain(exactly(3, decimal_digit())),
+        "body_bytes_sent"
+        | "bytes_sent"
+        | "request_length"
+        | "connection"
+        | "connection_requests"
+        | "remote_port"
+        | "server_port"
+        | "pid" => chain(number()),
+        _ => match next {
+            Some(next) => chain(zero_or_more(without_set(&[
+                regex::escape(&next.to_string()),
+                r"\n".to_string(),
+            ]))),
+            None => chain(zero_or_more(any())),
+        },
+    }
+}
+
+/// The priority at the start of a syslog message, such as `<34>`
+fn priority(naming: Naming) -> HumanRegex<SymbolChain> {
+    text("<") + naming.capture("priority", number_in_range(0..=191)) + text(">")
+}
+
+/// Matches a message in the BSD syslog format of RFC 3164, such as
+/// `<34>Oct 11 22:14:15 mymachine su[230]: 'su root' failed for lonvick on /dev/pts/8`
+/// ```
+/// use human_regex::{beginning, end, patterns::logs::syslog_rfc3164};
+/// let regex_string = beginning() + syslog_rfc3164() + end();
+/// assert!(regex_string.to_regex().is_match("<34>Oct 11 22:14:15 mymachine su: 'su root' failed"));
+/// assert!(regex_string.to_regex().is_match("Oct  1 08:00:01 web01 CRON[4182]: (root) CMD (run-parts)"));
+/// assert!(!regex_string.to_regex().is_match("<192>Oct 11 22:14:15 mymachine su: 'su root' failed"));
+/// ```
+///
+/// The priority is optional, as files written by syslog daemons leave it out.
+pub fn syslog_rfc3164() -> HumanRegex<SymbolChain> {
+    syslog_rfc3164_with(Naming::Unnamed)
+}
+
+/// Matches a message in the BSD syslog format, capturing the `priority`, `time`, `hostname`,
+/// `app`, `pid` and `message`
+/// ```
+/// use human_regex::{patterns::logs::syslog_rfc3164_with, patterns::Naming};
+/// let regex_string = syslog_rfc3164_with(Naming::Named);
+/// let caps = regex_string.to_regex().captures("Oct  1 08:00:01 web01 CRON[4182]: (root) CMD (run-parts)").unwrap();
+/// assert_eq!(&caps["time"], "Oct  1 08:00:01");
+/// assert_eq!(&caps["app"], "CRON");
+/// assert_eq!(&caps["pid"], "4182");
+/// assert_eq!(&caps["message"], "(root) CMD (run-parts)");
+/// ```
+pub fn syslog_rfc3164_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    let tag = naming.capture("app", one_or_more(without_set(&[r"\s", ":", r"\["])))
+        + zero_or_one(text("[") + naming.capture("pid", one_or_more(decimal_digit())) + text("]"))
+        + text(": ");
+    zero_or_one(priority(naming))
+        + naming.capture("time", from_strftime("%b %e %H:%M:%S").unwrap())
+        + text(" ")
+        + naming.capture("hostname", token())
+        + text(" ")
+        + zero_or_one(tag)
+        + naming.capture("message", zero_or_more(any()))
+}
+
+/// Between one and `most` printable ASCII characters other than the space, or `-` for no value
+fn printable(most: u8) -> HumanRegex<SymbolChain> {
+    chain(between(1, most, within_range('!'..='~')))
+}
+
+/// Matches a message in the syslog format of RFC 5424, such as
+/// `<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3"] An application event`
+/// ```
+/// use human_regex::{beginning, end, patterns::logs::syslog_rfc5424};
+/// let regex_string = beginning() + syslog_rfc5424() + end();
+/// assert!(regex_string.to_regex().is_match("<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - 'su root' failed"));
+/// assert!(regex_string.to_regex().is_match(r#"<165>1 - - - - - [a@1 b="x\"y" c="z"][d@2]"#));
+/// assert!(!regex_string.to_regex().is_match("<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47"));
+/// ```
+pub fn syslog_rfc5424() -> HumanRegex<SymbolChain> {
+    syslog_rfc5424_with(Naming::Unnamed)
+}
+
+/// Matches a message in the syslog format of RFC 5424, capturing the `priority`, `version`, `time`,
+/// `hostname`, `app`, `procid`, `msgid`, `structured_data` and `message`
+/// ```
+/// use human_regex::{patterns::logs::syslog_rfc5424_with, patterns::Naming};
+/// let regex_string = syslog_rfc5424_with(Naming::Named);
+/// let line = r#"<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3"] An application event"#;
+/// let caps = regex_string.to_regex().captures(line).unwrap();
+/// assert_eq!(&caps["priority"], "165");
+/// assert_eq!(&caps["app"], "evntslog");
+/// assert_eq!(&caps["procid"], "-");
+/// assert_eq!(&caps["structured_data"], r#"[exampleSDID@32473 iut="3"]"#);
+/// assert_eq!(&caps["message"], "An application event");
+/// ```
+pub fn syslog_rfc5424_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    // Printable ASCII other than `=`, the space, `]` and `"`
+    let sd_name = || between(1, 32, within_set(&["!", "#-<", r">-\\", r"\^-~"]));
+    // Inside a value, `"`, `\` and `]` have to be escaped with a `\`
+    let sd_value = zero_or_more(or(&[
+        chain(without_set(&["\"", r"\\", r"\]"])),
+        text(r"\") + any(),
+    ]));
+    let sd_element = text("[")
+        + sd_name()
+        + zero_or_more(text(" ") + sd_name() + text("=\"") + sd_value + text("\""))
+        + text("]");
+    priority(naming)
+        + naming.capture(
+            "version",
+            within_range('1'..='9') + between(0, 2, decimal_digit()),
+        )
+        + text(" ")
+        + naming.capture("time", or(&[chain(text("-")), rfc3339()]))
+        + text(" ")
+        + naming.capture("hostname", printable(255))
+        + text(" ")
+        + naming.capture("app", printable(48))
+        + text(" ")
+        + naming.capture("procid", printable(128))
+        + text(" ")
+        + naming.capture("msgid", printable(32))
+        + text(" ")
+        + naming.capture(
+            "structured_data",
+            or(&[chain(text("-")), chain(one_or_more(sd_element))]),
+        )
+        + zero_or_one(text(" ") + naming.capture("message", zero_or_more(any())))
+}
+
+/// Matches one `key=value` pair of a logfmt line, such as `level=info` or `msg="shutting down"`,
+/// or a key on its own
+/// ```
+/// use human_regex::{patterns::logs::logfmt_pair_with, patterns::Naming};
+/// let regex_string = logfmt_pair_with(Naming::Named);
+/// let pairs: Vec<(&str, &str)> = regex_string
+///     .to_regex()
+///     .captures_iter(r#"level=info msg="shutting down" took=1.5s debug"#)
+///     .map(|caps| (caps.name("key").unwrap().as_str(), caps.name("value").map_or("", |m| m.as_str())))
+///     .collect();
+/// assert_eq!(pairs, vec![("level", "info"), ("msg", r#""shutting down""#), ("took", "1.5s"), ("debug", "")]);
+/// ```
+pub fn logfmt_pair() -> HumanRegex<SymbolChain> {
+    logfmt_pair_with(Naming::Unnamed)
+}
+
+/// Matches one pair of a logfmt line, capturing the `key` and the `value`. A quoted value is
+/// captured with its quotes and escapes.
+pub fn logfmt_pair_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    let value = or(&[
+        text("\"") + quoted_text() + text("\""),
+        chain(zero_or_more(without_set(&[r"\s", "\""]))),
+    ]);
+    naming.capture("key", one_or_more(without_set(&[r"\s", "=", "\""])))
+        + zero_or_one(text("=") + naming.capture("value", value))
+}
+
+/// Matches a whole logfmt line, made of pairs separated by whitespace
+/// ```
+/// use human_regex::{beginning, end, patterns::logs::logfmt_line};
+/// let regex_string = beginning() + logfmt_line() + end();
+/// assert!(regex_string.to_regex().is_match(r#"ts=2023-10-05T14:30:15Z level=warn msg="disk \"sda\" almost full""#));
+/// assert!(!regex_string.to_regex().is_match(r#"level=warn msg="unterminated"#));
+/// ```
+pub fn logfmt_line() -> HumanRegex<SymbolChain> {
+    logfmt_pair() + zero_or_more(one_or_more(whitespace()) + logfmt_pair())
+}
@@ -0,0 +1,205 @@
+//! Patterns for the tags, attributes, comments and CDATA sections of HTML and XML
+//!
+//! Attribute values are matched as a whole, quotes included, so a `>` inside a quoted value does
+//! not end the tag.
+//! ```
+//! use human_regex::patterns::{markup::open_tag_with, Naming};
+//! let regex_string = open_tag_with(None, Naming::Named);
+//! let html = r#"<p class="note" title="1 > 0">Hi</p>"#;
+//! let caps = regex_string.to_regex().captures(html).unwrap();
+//! assert_eq!(&caps[0], r#"<p class="note" title="1 > 0">"#);
+//! assert_eq!(&caps["tag"], "p");
+//! ```
+
+use super::{chain, Naming};
+use crate::humanregex::*;
+use crate::{
+    any, case_insensitive, dot_matches_newline_too, one_or_more, or, text, whitespace, within_set,
+    without_set, zero_or_more, zero_or_one,
+};
+
+/// A tag name, which is the given one in any case or, without one, any name
+fn tag_name(name: Option<&str>) -> HumanRegex<SymbolChain> {
+    match name {
+        Some(name) => case_insensitive(text(name)),
+        None => {
+            within_set(&["A-Z", "a-z"])
+                + zero_or_more(within_set(&["A-Z", "a-z", "0-9", r"\.", "_", ":", r"\-"]))
+        }
+    }
+}
+
+/// The attributes of a tag, each after some whitespace
+fn attributes() -> HumanRegex<SymbolChain> {
+    chain(zero_or_more(one_or_more(whitespace()) + attribute(None)))
+}
+
+/// Matches an opening tag, such as `<a href="/">`, with the given name or any name
+/// ```
+/// use human_regex::{beginning, end, patterns::markup::open_tag};
+/// let regex_string = beginning() + open_tag(Some("div")) + end();
+/// assert!(regex_string.to_regex().is_match("<div>"));
+/// assert!(regex_string.to_regex().is_match("<DIV id=main data-x='a > b'>"));
+/// assert!(!regex_string.to_regex().is_match("<divider>"));
+/// assert!(!regex_string.to_regex().is_match("<div/>"));
+/// ```
+///
+/// A given name matches in any case, as HTML tag names do.
+pub fn open_tag(name: Option<&str>) -> HumanRegex<SymbolChain> {
+    open_tag_with(name, Naming::Unnamed)
+}
+
+/// Matches an opening tag, capturing its `tag` name and its `attributes` as written
+/// ```
+/// use human_regex::patterns::{markup::{attribute_with, open_tag_with, unquoted}, Naming};
+/// let tags = open_tag_with(Some("a"), Naming::Named).to_regex();
+/// let hrefs = attribute_with(Some("href"), Naming::Named).to_regex();
+/// let html = r#"<a class=nav href="/home">Home</a> <a href='/about'>About</a>"#;
+/// let links: Vec<&str> = tags
+///     .captures_iter(html)
+///     .filter_map(|tag| hrefs.captures(tag.name("attributes").unwrap().as_str()))
+///     .map(|href| unquoted(href.name("value").unwrap().as_str()))
+///     .collect();
+/// assert_eq!(links, vec!["/home", "/about"]);
+/// ```
+pub fn open_tag_with(name: Option<&str>, naming: Naming) -> HumanRegex<SymbolChain> {
+    text("<")
+        + naming.capture("tag", tag_name(name))
+        + naming.capture("attributes", attributes())
+        + zero_or_more(whitespace())
+        + text(">")
+}
+
+/// Matches a closing tag, such as `</a>`, with the given name or any name
+/// ```
+/// use human_regex::{beginning, end, patterns::markup::close_tag};
+/// let regex_string = beginning() + close_tag(Some("div")) + end();
+/// assert!(regex_string.to_regex().is_match("</div>"));
+/// assert!(regex_string.to_regex().is_match("</Div >"));
+/// assert!(!regex_string.to_regex().is_match("<div>"));
+/// ```
+pub fn close_tag(name: Option<&str>) -> HumanRegex<SymbolChain> {
+    close_tag_with(name, Naming::Unnamed)
+}
+
+/// Matches a closing tag, capturing its `tag` name
+pub fn close_tag_with(name: Option<&str>, naming: Naming) -> HumanRegex<SymbolChain> {
+    text("</") + naming.capture("tag", tag_name(name)) + zero_or_more(whitespace()) + text(">")
+}
+
+/// Matches a self-closing tag, such as `<br/>` or `<img src="a.png" />`, with the given name or
+/// any name
+/// ```
+/// use human_regex::{beginning, end, patterns::markup::self_closing_tag};
+/// let regex_string = beginning() + self_closing_tag(None) + end();
+/// assert!(regex_string.to_regex().is_match("<br/>"));
+/// assert!(regex_string.to_regex().is_match(r#"<img src="a.png" alt="a/>b" />"#));
+/// assert!(regex_string.to_regex().is_match("<link href=/style.css/>"));
+/// assert!(!regex_string.to_regex().is_match("<br>"));
+/// ```
+pub fn self_closing_tag(name: Option<&str>) -> HumanRegex<SymbolChain> {
+    self_closing_tag_with(name, Naming::Unnamed)
+}
+
+/// Matches a self-closing tag, capturing its `tag` name and its `attributes` as written
+pub fn self_closing_tag_with(name: Option<&str>, naming: Naming) -> HumanRegex<SymbolChain> {
+    text("<")
+        + naming.capture("tag", tag_name(name))
+        + naming.capture("attributes", attributes())
+        + zero_or_more(whitespace())
+        + text("/>")
+}
+
+/// Matches an attribute, such as `href="/"`, `checked` or `width=80`, with the given name or any
+/// name
+/// ```
+/// use human_regex::{beginning, end, patterns::markup::attribute};
+/// let regex_string = beginning() + attribute(None) + end();
+/// for written in [r#"href="/""#, "checked", "width = 80", "title='say \"hi\"'"] {
+///     assert!(regex_string.to_regex().is_match(written));
+/// }
+/// assert!(!regex_string.to_regex().is_match(r#"href="/"#));
+/// ```
+///
+/// A given name matches in any case, as HTML attribute names do.
+pub fn attribute(name: Option<&str>) -> HumanRegex<SymbolChain> {
+    attribute_with(name, Naming::Unnamed)
+}
+
+/// Matches an attribute, capturing its `name` and its `value` as written, quotes included, which
+/// [unquoted] removes
+pub fn attribute_with(name: Option<&str>, naming: Naming) -> HumanRegex<SymbolChain> {
+    let name = match name {
+        Some(name) => case_insensitive(text(name)),
+        None => chain(one_or_more(without_set(&[r"\s", "\"", "'", ">", "/", "="]))),
+    };
+    let value = or(&[
+        text("\"") + zero_or_more(without_set(&["\""])) + text("\""),
+        text("'") + zero_or_more(without_set(&["'"])) + text("'"),
+        chain(one_or_more(without_set(&[
+            r"\s", "\"", "'", "=", "<", ">", "`",
+        ]))),
+    ]);
+    naming.capture("name", name)
+        + zero_or_one(
+            zero_or_more(whitespace())
+                + text("=")
+                + zero_or_more(whitespace())
+                + naming.capture("value", value),
+        )
+}
+
+/// Removes the single or double quotes around an attribute value, leaving an unquoted value as it
+/// is
+/// ```
+/// use human_regex::patterns::markup::unquoted;
+/// assert_eq!(unquoted(r#""a 'b'""#), "a 'b'");
+/// assert_eq!(unquoted("'/'"), "/");
+/// assert_eq!(unquoted("80"), "80");
+/// ```
+pub fn unquoted(value: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|&quote| value.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(value)
+}
+
+/// Matches a comment, such as `<!-- note -->`, which may span lines
+/// ```
+/// use human_regex::patterns::markup::comment;
+/// let found: Vec<&str> = comment().to_regex().find_iter("<!-- a --> b <!--\nc\n-->").map(|m| m.as_str()).collect();
+/// assert_eq!(found, vec!["<!-- a -->", "<!--\nc\n-->"]);
+/// ```
+pub fn comment() -> HumanRegex<SymbolChain> {
+    comment_with(Naming::Unnamed)
+}
+
+/// Matches a comment, capturing the `content` between its delimiters
+pub fn comment_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    text("<!--")
+        + naming.capture(
+            "content",
+            zero_or_more(dot_matches_newline_too(any())).lazy(),
+        )
+        + text("-->")
+}
+
+/// Matches a CDATA section, such as `<![CDATA[a < b]]>`, which may span lines
+/// ```
+/// use human_regex::patterns::{markup::cdata_with, Naming};
+/// let caps = cdata_with(Naming::Named).to_regex().captures("<x><![CDATA[if (a < b) {}]]></x>").unwrap();
+/// assert_eq!(&caps["content"], "if (a < b) {}");
+/// ```
+pub fn cdata() -> HumanRegex<SymbolChain> {
+    cdata_with(Naming::Unnamed)
+}
+
+/// Matches a CDATA section, capturing the `content` between its delimiters
+pub fn cdata_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    text("<![CDATA[")
+        + naming.capture(
+            "content",
+            zero_or_more(dot_matches_newline_too(any())).lazy(),
+        )
+        + text("]]>")
+}
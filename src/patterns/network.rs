@@ -0,0 +1,237 @@
+//! Patterns for IP addresses, CIDR blocks and MAC addresses
+//!
+//! Numeric limits such as the 0–255 range of an IPv4 octet are part of the regex itself. The
+//! patterns are not anchored, so surround them with [beginning](crate::beginning) and
+//! [end](crate::end) or [word_boundary](crate::word_boundary) to keep them from matching inside a
+//! longer run of digits.
+
+use super::{chain, Naming};
+use crate::humanregex::*;
+use crate::{
+    alphanumeric, between, exactly, hexdigit, one_or_more, or, text, within_range, within_set,
+    zero_or_one,
+};
+
+/// A single ASCII decimal digit
+fn decimal() -> HumanRegex<SymbolClass<Custom>> {
+    within_range('0'..='9')
+}
+
+/// A decimal number from 0 to 255 without leading zeros
+fn octet() -> HumanRegex<SymbolChain> {
+    or(&[
+        text("25") + within_range('0'..='5'),
+        text("2") + within_range('0'..='4') + decimal(),
+        text("1") + exactly(2, decimal()),
+        zero_or_one(within_range('1'..='9')) + decimal(),
+    ])
+}
+
+/// Matches an IPv4 address in dotted-decimal form, such as `192.168.0.1`
+/// ```
+/// use human_regex::{beginning, end, patterns::network::ipv4};
+/// let regex_string = beginning() + ipv4() + end();
+/// assert!(regex_string.to_regex().is_match("192.168.0.1"));
+/// assert!(regex_string.to_regex().is_match("255.255.255.255"));
+/// assert!(!regex_string.to_regex().is_match("256.1.1.1"));
+/// assert!(!regex_string.to_regex().is_match("01.2.3.4"));
+/// ```
+pub fn ipv4() -> HumanRegex<SymbolChain> {
+    ipv4_with(Naming::Unnamed)
+}
+
+/// Matches an IPv4 address, capturing its octets as `octet1` to `octet4`
+/// ```
+/// use human_regex::{patterns::network::ipv4_with, patterns::Naming};
+/// let caps = ipv4_with(Naming::Named).to_regex().captures("gateway 10.0.0.1").unwrap();
+/// assert_eq!(&caps["octet1"], "10");
+/// assert_eq!(&caps["octet4"], "1");
+/// ```
+pub fn ipv4_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    naming.capture("octet1", octet())
+        + text(".")
+        + naming.capture("octet2", octet())
+        + text(".")
+        + naming.capture("octet3", octet())
+        + text(".")
+        + naming.capture("octet4", octet())
+}
+
+/// A group of one to four hexadecimal digits in an IPv6 address
+fn hextet() -> HumanRegex<SymbolChain> {
+    chain(between(1, 4, hexdigit()))
+}
+
+/// A run of `n` colon-separated hextets, which is empty when `n` is zero
+fn hextets(n: u8) -> HumanRegex<SymbolChain> {
+    match n {
+        0 => chain(text("")),
+        _ => hextet() + exactly(n - 1, text(":") + hextet()),
+    }
+}
+
+/// The alternatives for an IPv6 address made of `groups` hextets, some of which may be compressed
+/// to `::`, leaving room for an embedded IPv4 address after them when `embedded` is set
+fn ipv6_forms(groups: u8, embedded: bool) -> Vec<HumanRegex<SymbolChain>> {
+    let mut forms = Vec::new();
+    // The uncompressed form, then the forms with `left` hextets before the `::`
+    if embedded {
+        forms.push(chain(exactly(groups, hextet() + text(":"))));
+    } else {
+        forms.push(hextets(groups));
+    }
+    for left in 0..groups {
+        let right = groups - 1 - left;
+        let after = if embedded {
+            chain(between(0, right, hextet() + text(":")))
+        } else if right > 0 {
+            chain(zero_or_one(
+                hextet() + between(0, right - 1, text(":") + hextet()),
+            ))
+        } else {
+            chain(text(""))
+        };
+        forms.push(hextets(left) + text("::") + after);
+    }
+    forms
+}
+
+/// An IPv6 address, optionally followed by a zone ID
+fn ipv6_address(naming: Naming, zone: bool) -> HumanRegex<SymbolChain> {
+    // Addresses that end in an embedded IPv4 address come first, so that the IPv4 part is not
+    // taken for a truncated hextet
+    let address = or(&[
+        or(&ipv6_forms(6, true)) + naming.capture("ipv4", ipv4()),
+        or(&ipv6_forms(8, false)),
+    ]);
+    if !zone {
+        return address;
+    }
+    address
+        + zero_or_one(
+            text("%")
+                + naming.capture(
+                    "zone",
+                    one_or_more(or(&[
+                        chain(alphanumeric()),
+                        chain(within_set(&['.', '_', '~', '-'])),
+                    ])),
+                ),
+        )
+}
+
+/// Matches an IPv6 address in any of its forms: full, compressed with `::`, ending in an embedded
+/// IPv4 address, and followed by a `%` zone ID
+/// ```
+/// use human_regex::{beginning, end, patterns::network::ipv6};
+/// let regex_string = beginning() + ipv6() + end();
+/// for address in ["2001:db8:0:0:0:0:2:1", "2001:db8::2:1", "::1", "::", "fe80::1%eth0", "::ffff:192.0.2.128"] {
+///     assert!(regex_string.to_regex().is_match(address), "{}", address);
+/// }
+/// for address in ["2001:db8::2::1", "1:2:3:4:5:6:7:8:9", "12345::", "::ffff:192.0.2.256"] {
+///     assert!(!regex_string.to_regex().is_match(address), "{}", address);
+/// }
+/// ```
+pub fn ipv6() -> HumanRegex<SymbolChain> {
+    ipv6_with(Naming::Unnamed)
+}
+
+/// Matches an IPv6 address, capturing an embedded IPv4 address as `ipv4` and a zone ID as `zone`
+/// ```
+/// use human_regex::{patterns::network::ipv6_with, patterns::Naming};
+/// let caps = ipv6_with(Naming::Named).to_regex().captures("::ffff:10.0.0.1%2").unwrap();
+/// assert_eq!(&caps["ipv4"], "10.0.0.1");
+/// assert_eq!(&caps["zone"], "2");
+/// ```
+pub fn ipv6_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    ipv6_address(naming, true)
+}
+
+/// Matches an IPv4 CIDR block, such as `10.0.0.0/8`, with a prefix length from 0 to 32
+/// ```
+/// use human_regex::{beginning, end, patterns::network::cidr_v4};
+/// let regex_string = beginning() + cidr_v4() + end();
+/// assert!(regex_string.to_regex().is_match("10.0.0.0/8"));
+/// assert!(regex_string.to_regex().is_match("192.168.1.0/32"));
+/// assert!(!regex_string.to_regex().is_match("192.168.1.0/33"));
+/// ```
+pub fn cidr_v4() -> HumanRegex<SymbolChain> {
+    cidr_v4_with(Naming::Unnamed)
+}
+
+/// Matches an IPv4 CIDR block, capturing `address` with its octets and the `prefix` length
+/// ```
+/// use human_regex::{patterns::network::cidr_v4_with, patterns::Naming};
+/// let caps = cidr_v4_with(Naming::Named).to_regex().captures("172.16.0.0/12").unwrap();
+/// assert_eq!(&caps["address"], "172.16.0.0");
+/// assert_eq!(&caps["octet2"], "16");
+/// assert_eq!(&caps["prefix"], "12");
+/// ```
+pub fn cidr_v4_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    let prefix = or(&[
+        text("3") + within_range('0'..='2'),
+        zero_or_one(within_range('1'..='2')) + decimal(),
+    ]);
+    naming.capture("address", ipv4_with(naming)) + text("/") + naming.capture("prefix", prefix)
+}
+
+/// Matches an IPv6 CIDR block, such as `2001:db8::/32`, with a prefix length from 0 to 128
+/// ```
+/// use human_regex::{beginning, end, patterns::network::cidr_v6};
+/// let regex_string = beginning() + cidr_v6() + end();
+/// assert!(regex_string.to_regex().is_match("2001:db8::/32"));
+/// assert!(regex_string.to_regex().is_match("::/0"));
+/// assert!(!regex_string.to_regex().is_match("2001:db8::/129"));
+/// ```
+pub fn cidr_v6() -> HumanRegex<SymbolChain> {
+    cidr_v6_with(Naming::Unnamed)
+}
+
+/// Matches an IPv6 CIDR block, capturing the `address`, an embedded `ipv4` address and the
+/// `prefix` length
+/// ```
+/// use human_regex::{patterns::network::cidr_v6_with, patterns::Naming};
+/// let caps = cidr_v6_with(Naming::Named).to_regex().captures("fd00::/8").unwrap();
+/// assert_eq!(&caps["address"], "fd00::");
+/// assert_eq!(&caps["prefix"], "8");
+/// ```
+pub fn cidr_v6_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    let prefix = or(&[
+        text("12") + within_range('0'..='8'),
+        text("1") + within_range('0'..='1') + decimal(),
+        zero_or_one(within_range('1'..='9')) + decimal(),
+    ]);
+    naming.capture("address", ipv6_address(naming, false))
+        + text("/")
+        + naming.capture("prefix", prefix)
+}
+
+/// Matches a MAC address written as six pairs of hexadecimal digits joined by the separator,
+/// such as `00:1a:2b:3c:4d:5e` or `00-1A-2B-3C-4D-5E`
+/// ```
+/// use human_regex::{beginning, end, patterns::network::mac_address};
+/// let regex_string = beginning() + mac_address(':') + end();
+/// assert!(regex_string.to_regex().is_match("00:1a:2b:3c:4d:5e"));
+/// assert!(!regex_string.to_regex().is_match("00-1a-2b-3c-4d-5e"));
+/// assert!(!regex_string.to_regex().is_match("00:1a:2b:3c:4d"));
+/// ```
+pub fn mac_address(separator: char) -> HumanRegex<SymbolChain> {
+    mac_address_with(separator, Naming::Unnamed)
+}
+
+/// Matches a MAC address, capturing its pairs of hexadecimal digits as `octet1` to `octet6`
+/// ```
+/// use human_regex::{patterns::network::mac_address_with, patterns::Naming};
+/// let caps = mac_address_with('-', Naming::Named).to_regex().captures("00-1A-2B-3C-4D-5E").unwrap();
+/// assert_eq!(&caps["octet1"], "00");
+/// assert_eq!(&caps["octet6"], "5E");
+/// ```
+pub fn mac_address_with(separator: char, naming: Naming) -> HumanRegex<SymbolChain> {
+    let mut regex_string = naming.capture("octet1", exactly(2, hexdigit()));
+    for index in 2..=6 {
+        regex_string = regex_string
+            + text(separator.to_string())
+            + naming.capture(&format!("octet{}", index), exactly(2, hexdigit()));
+    }
+    regex_string
+}
@@ -0,0 +1,265 @@
+//! Patterns for numbers as people and programming languages write them
+//!
+//! [integer], [decimal] and [scientific] follow a [NumberFormat], which sets the decimal mark,
+//! the separator between groups of thousands, and the names of the capture groups for the `sign`,
+//! `integer` part, `fraction` and `exponent`.
+//! ```
+//! use human_regex::{beginning, end, patterns::numbers::{decimal, NumberFormat}, patterns::Naming};
+//! let format = NumberFormat { naming: Naming::Named, ..NumberFormat::european() };
+//! let caps = (beginning() + decimal(format) + end()).to_regex().captures("-1.234.567,89").unwrap();
+//! assert_eq!(&caps["sign"], "-");
+//! assert_eq!(&caps["integer"], "1.234.567");
+//! assert_eq!(&caps["fraction"], "89");
+//! ```
+
+use super::{chain, Naming};
+use crate::humanregex::*;
+use crate::{
+    between, exactly, one_or_more, or, text, within_range, within_set, zero_or_more, zero_or_one,
+};
+
+/// How numbers are written in a particular locale, and which components to capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat<'a> {
+    /// The character between the integer part and the fraction
+    pub decimal_mark: char,
+    /// The character between groups of three digits in the integer part, which may also be left out
+    pub thousands_separator: Option<char>,
+    /// Whether to capture the `sign`, `integer`, `fraction` and `exponent`
+    pub naming: Naming<'a>,
+}
+
+impl Default for NumberFormat<'_> {
+    /// A `.` decimal mark without thousands separators, as in `1234567.89`
+    fn default() -> Self {
+        NumberFormat {
+            decimal_mark: '.',
+            thousands_separator: None,
+            naming: Naming::Unnamed,
+        }
+    }
+}
+
+impl NumberFormat<'_> {
+    /// A `.` decimal mark with `,` between thousands, as in `1,234,567.89`
+    pub fn english() -> Self {
+        NumberFormat {
+            thousands_separator: Some(','),
+            ..Default::default()
+        }
+    }
+
+    /// A `,` decimal mark with `.` between thousands, as in `1.234.567,89`
+    pub fn european() -> Self {
+        NumberFormat {
+            decimal_mark: ',',
+            thousands_separator: Some('.'),
+            ..Default::default()
+        }
+    }
+
+    /// A `,` decimal mark with a thin space (U+2009) between thousands, as in `1 234 567,89`
+    pub fn thin_space() -> Self {
+        NumberFormat {
+            decimal_mark: ',',
+            thousands_separator: Some('\u{2009}'),
+            ..Default::default()
+        }
+    }
+}
+
+/// A single ASCII decimal digit
+fn decimal_digit() -> HumanRegex<SymbolClass<Custom>> {
+    within_range('0'..='9')
+}
+
+/// An optional `+` or `-`
+fn sign(naming: Naming) -> HumanRegex<SymbolChain> {
+    chain(zero_or_one(naming.capture("sign", within_set(&['+', '-']))))
+}
+
+/// The digits before the decimal mark, grouped in threes or not at all
+fn integer_part(format: NumberFormat) -> HumanRegex<SymbolChain> {
+    let digits = match format.thousands_separator {
+        Some(separator) => or(&[
+            chain(
+                between(1, 3, decimal_digit())
+                    + one_or_more(text(separator.to_string()) + exactly(3, decimal_digit())),
+            ),
+            chain(one_or_more(decimal_digit())),
+        ]),
+        None => chain(one_or_more(decimal_digit())),
+    };
+    format.naming.capture("integer", digits)
+}
+
+/// The decimal mark and the digits after it
+fn fraction(format: NumberFormat) -> HumanRegex<SymbolChain> {
+    text(format.decimal_mark.to_string())
+        + format
+            .naming
+            .capture("fraction", one_or_more(decimal_digit()))
+}
+
+/// An `e` or `E` followed by a signed power of ten
+fn exponent(format: NumberFormat) -> HumanRegex<SymbolChain> {
+    within_set(&['e', 'E'])
+        + format.naming.capture(
+            "exponent",
+            zero_or_one(within_set(&['+', '-'])) + one_or_more(decimal_digit()),
+        )
+}
+
+/// Matches a whole number with an optional sign, such as `-42` or `1,000,000`
+/// ```
+/// use human_regex::{beginning, end, patterns::numbers::{integer, NumberFormat}};
+/// let regex_string = beginning() + integer(NumberFormat::english()) + end();
+/// assert!(regex_string.to_regex().is_match("1,000,000"));
+/// assert!(regex_string.to_regex().is_match("+1000000"));
+/// assert!(!regex_string.to_regex().is_match("1,00,000"));
+/// ```
+pub fn integer(format: NumberFormat) -> HumanRegex<SymbolChain> {
+    sign(format.naming) + integer_part(format)
+}
+
+/// Matches a number with an optional sign and an optional fraction, such as `3.14` or `-2`
+/// ```
+/// use human_regex::{beginning, end, patterns::numbers::{decimal, NumberFormat}};
+/// let regex_string = beginning() + decimal(NumberFormat::thin_space()) + end();
+/// assert!(regex_string.to_regex().is_match("1\u{2009}234,5"));
+/// assert!(regex_string.to_regex().is_match("-0,25"));
+/// assert!(!regex_string.to_regex().is_match("0.25"));
+/// ```
+pub fn decimal(format: NumberFormat) -> HumanRegex<SymbolChain> {
+    integer(format) + zero_or_one(fraction(format))
+}
+
+/// Matches a number in scientific notation, such as `6.022e23` or `-1E-9`
+/// ```
+/// use human_regex::{beginning, end, patterns::numbers::{scientific, NumberFormat}, patterns::Naming};
+/// let format = NumberFormat { naming: Naming::Named, ..Default::default() };
+/// let caps = (beginning() + scientific(format) + end()).to_regex().captures("6.022e+23").unwrap();
+/// assert_eq!(&caps["integer"], "6");
+/// assert_eq!(&caps["fraction"], "022");
+/// assert_eq!(&caps["exponent"], "+23");
+/// assert!(!scientific(format).to_regex().is_match("6.022"));
+/// ```
+pub fn scientific(format: NumberFormat) -> HumanRegex<SymbolChain> {
+    decimal(format) + exponent(format)
+}
+
+/// The base of an integer literal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    /// Base 2, with a `0b` prefix
+    Binary,
+    /// Base 8, with a `0o` prefix, or just a leading `0` in C
+    Octal,
+    /// Base 16, with a `0x` prefix
+    Hexadecimal,
+}
+
+/// The programming language whose rules an integer literal follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiteralSyntax {
+    /// Lowercase prefixes, underscores anywhere after the prefix, and an optional type suffix
+    /// such as `u8`
+    Rust,
+    /// Prefixes in either case, `'` between digits as in C23, and an optional suffix such as `UL`
+    C,
+    /// Prefixes in either case, and single underscores between digits or after the prefix
+    Python,
+}
+
+/// Matches a binary, octal or hexadecimal integer literal, such as `0xFF_FF`
+/// ```
+/// use human_regex::{beginning, end, patterns::numbers::{radix_literal, LiteralSyntax, Radix}};
+/// let rust = beginning() + radix_literal(Radix::Hexadecimal, LiteralSyntax::Rust) + end();
+/// assert!(rust.to_regex().is_match("0xdead_beef_u32"));
+/// assert!(!rust.to_regex().is_match("0XFF"));
+///
+/// let c = beginning() + radix_literal(Radix::Octal, LiteralSyntax::C) + end();
+/// assert!(c.to_regex().is_match("0755"));
+/// assert!(!c.to_regex().is_match("0789"));
+///
+/// let python = beginning() + radix_literal(Radix::Binary, LiteralSyntax::Python) + end();
+/// assert!(python.to_regex().is_match("0B1010_0101"));
+/// assert!(!python.to_regex().is_match("0b1010__0101"));
+/// ```
+pub fn radix_literal(radix: Radix, syntax: LiteralSyntax) -> HumanRegex<SymbolChain> {
+    radix_literal_with(radix, syntax, Naming::Unnamed)
+}
+
+/// Matches a binary, octal or hexadecimal integer literal, capturing its digits as `integer` and
+/// any type suffix as `suffix`
+/// ```
+/// use human_regex::{patterns::numbers::{radix_literal_with, LiteralSyntax, Radix}, patterns::Naming};
+/// let regex_string = radix_literal_with(Radix::Hexadecimal, LiteralSyntax::C, Naming::Named);
+/// let caps = regex_string.to_regex().captures("mask = 0xFF'FFUL;").unwrap();
+/// assert_eq!(&caps["integer"], "FF'FF");
+/// assert_eq!(&caps["suffix"], "UL");
+/// ```
+pub fn radix_literal_with(
+    radix: Radix,
+    syntax: LiteralSyntax,
+    naming: Naming,
+) -> HumanRegex<SymbolChain> {
+    let digit = || match radix {
+        Radix::Binary => chain(within_range('0'..='1')),
+        Radix::Octal => chain(within_range('0'..='7')),
+        Radix::Hexadecimal => or(&[
+            chain(within_range('0'..='9')),
+            chain(within_range('a'..='f')),
+            chain(within_range('A'..='F')),
+        ]),
+    };
+    let letter = match radix {
+        Radix::Binary => 'b',
+        Radix::Octal => 'o',
+        Radix::Hexadecimal => 'x',
+    };
+    let prefix = match syntax {
+        LiteralSyntax::Rust => chain(text(format!("0{}", letter))),
+        LiteralSyntax::C if radix == Radix::Octal => chain(text("0")),
+        LiteralSyntax::C | LiteralSyntax::Python => {
+            text("0") + within_set(&[letter, letter.to_ascii_uppercase()])
+        }
+    };
+    match syntax {
+        LiteralSyntax::Rust => {
+            let suffix = or(&["i", "u"]
+                .iter()
+                .flat_map(|kind| {
+                    ["8", "16", "32", "64", "128", "size"]
+                        .map(|size| text(format!("{}{}", kind, size)))
+                })
+                .collect::<Vec<_>>());
+            let digits =
+                zero_or_more(text("_")) + digit() + zero_or_more(or(&[digit(), chain(text("_"))]));
+            prefix
+                + naming.capture("integer", digits)
+                + zero_or_one(naming.capture("suffix", suffix))
+        }
+        LiteralSyntax::C => {
+            let unsigned = || within_set(&['u', 'U']);
+            let long = || or(&["ll", "LL", "l", "L"]);
+            let suffix = or(&[
+                unsigned() + zero_or_one(long()),
+                long() + zero_or_one(unsigned()),
+            ]);
+            let digits = digit() + zero_or_more(zero_or_one(text("'")) + digit());
+            // The leading `0` of an octal literal is its prefix, so the digits after it may be empty
+            let digits = if radix == Radix::Octal {
+                chain(zero_or_one(digits))
+            } else {
+                digits
+            };
+            prefix
+                + naming.capture("integer", digits)
+                + zero_or_one(naming.capture("suffix", suffix))
+        }
+        LiteralSyntax::Python => {
+            prefix + naming.capture("integer", one_or_more(zero_or_one(text("_")) + digit()))
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Patterns for file system paths and file names
+//!
+//! The path builders capture the `directory`, including its trailing separator, along with the
+//! `stem` and `extension` of the file name. The extension is everything after the first dot that
+//! does not start the name, so `archive.tar.gz` has the stem `archive` and the extension `tar.gz`,
+//! and `.bashrc` has no extension.
+//! ```
+//! use human_regex::{beginning, end, patterns::paths::posix_path_with, patterns::Naming};
+//! let regex_string = beginning() + posix_path_with(Naming::Named) + end();
+//! let caps = regex_string.to_regex().captures("target/release/build/archive.tar.gz").unwrap();
+//! assert_eq!(&caps["directory"], "target/release/build/");
+//! assert_eq!(&caps["stem"], "archive");
+//! assert_eq!(&caps["extension"], "tar.gz");
+//! ```
+
+use super::{chain, Naming};
+use crate::humanregex::*;
+use crate::{
+    escape_all, one_or_more, or, text, within_set, without_set, zero_or_more, zero_or_one,
+};
+
+/// The conventions of an operating system for writing paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStyle {
+    /// Components separated by `/`, which may contain anything other than `/` and NUL
+    Posix,
+    /// Components separated by `\` or `/`, which may not contain control characters or any of
+    /// `<>:"/\|?*`
+    Windows,
+}
+
+/// The characters that may not appear in a component, as members of a set
+fn reserved(style: PathStyle) -> Vec<&'static str> {
+    match style {
+        PathStyle::Posix => vec![r"\x00", "/"],
+        PathStyle::Windows => vec![
+            r"\x00-\x1F",
+            "<",
+            ">",
+            ":",
+            "\"",
+            "/",
+            r"\\",
+            r"\|",
+            r"\?",
+            r"\*",
+        ],
+    }
+}
+
+/// Matches a character that may appear in a component of a path, which rules out separators
+/// ```
+/// use human_regex::{beginning, end, one_or_more, patterns::paths::{path_character, PathStyle}};
+/// let regex_string = beginning() + one_or_more(path_character(PathStyle::Windows)) + end();
+/// assert!(regex_string.to_regex().is_match("Program Files (x86)"));
+/// assert!(!regex_string.to_regex().is_match(r"Program Files\Common Files"));
+/// assert!(!regex_string.to_regex().is_match("what?"));
+/// ```
+pub fn path_character(style: PathStyle) -> HumanRegex<SymbolClass<Custom>> {
+    without_set(&reserved(style))
+}
+
+/// A character that may appear in a component, other than a dot
+fn undotted_character(style: PathStyle) -> HumanRegex<SymbolClass<Custom>> {
+    let mut excluded = reserved(style);
+    excluded.push(r"\.");
+    without_set(&excluded)
+}
+
+/// Matches one component of a path, such as a directory or file name
+/// ```
+/// use human_regex::{patterns::paths::{path_component, PathStyle}};
+/// let regex_string = path_component(PathStyle::Posix);
+/// let components: Vec<&str> = regex_string.to_regex().find_iter("/usr/local/lib").map(|m| m.as_str()).collect();
+/// assert_eq!(components, vec!["usr", "local", "lib"]);
+/// ```
+pub fn path_component(style: PathStyle) -> HumanRegex<SymbolChain> {
+    chain(one_or_more(path_character(style)))
+}
+
+/// Matches a separator between the components of a path
+/// ```
+/// use human_regex::{beginning, end, patterns::paths::{separator, PathStyle}};
+/// assert!((beginning() + separator(PathStyle::Windows) + end()).to_regex().is_match(r"\"));
+/// assert!((beginning() + separator(PathStyle::Windows) + end()).to_regex().is_match("/"));
+/// assert!(!(beginning() + separator(PathStyle::Posix) + end()).to_regex().is_match(r"\"));
+/// ```
+pub fn separator(style: PathStyle) -> HumanRegex<SymbolClass<Custom>> {
+    match style {
+        PathStyle::Posix => within_set(&["/"]),
+        PathStyle::Windows => within_set(&[r"\\", "/"]),
+    }
+}
+
+/// Matches a file extension including its dot, such as `.rs` or `.toml`, from the given list
+/// ```
+/// use human_regex::{any, beginning, end, one_or_more, patterns::paths::file_extension};
+/// let regex_string = beginning() + one_or_more(any()) + file_extension(&["rs", "toml"]) + end();
+/// assert!(regex_string.to_regex().is_match("src/main.rs"));
+/// assert!(regex_string.to_regex().is_match("Cargo.toml"));
+/// assert!(!regex_string.to_regex().is_match("Cargo.lock"));
+/// assert!(!regex_string.to_regex().is_match("src/mainxrs"));
+/// ```
+///
+/// The extensions are escaped, so `tar.gz` only matches a literal dot.
+pub fn file_extension(extensions: &[&str]) -> HumanRegex<SymbolChain> {
+    file_extension_with(extensions, Naming::Unnamed)
+}
+
+/// Matches a file extension from the given list, capturing it without its dot as `extension`
+/// ```
+/// use human_regex::{beginning, end, patterns::paths::{file_extension_with, path_character, PathStyle}, patterns::Naming};
+/// use human_regex::{named_capture, one_or_more};
+/// let regex_string = beginning()
+///     + named_capture(one_or_more(path_character(PathStyle::Posix)), "stem")
+///     + file_extension_with(&["so", "dylib", "dll"], Naming::Named)
+///     + end();
+/// let caps = regex_string.to_regex().captures("libhuman_regex.dylib").unwrap();
+/// assert_eq!(&caps["stem"], "libhuman_regex");
+/// assert_eq!(&caps["extension"], "dylib");
+/// ```
+pub fn file_extension_with(extensions: &[&str], naming: Naming) -> HumanRegex<SymbolChain> {
+    text(".") + naming.capture("extension", or(&escape_all(extensions)))
+}
+
+/// A file name, capturing its `stem` and `extension`
+fn file_name(style: PathStyle, naming: Naming) -> HumanRegex<SymbolChain> {
+    // A leading dot belongs to the stem, and `.` and `..` have no extension
+    let stem = or(&[
+        zero_or_one(text(".")) + one_or_more(undotted_character(style)),
+        chain(text("..")),
+        chain(text(".")),
+    ]);
+    naming.capture("stem", stem)
+        + zero_or_one(text(".") + naming.capture("extension", zero_or_more(path_character(style))))
+}
+
+/// The components of a directory after any root or prefix, each followed by a separator
+fn directories(style: PathStyle) -> HumanRegex<SymbolChain> {
+    chain(zero_or_more(path_component(style) + separator(style)))
+}
+
+/// Matches an absolute or relative POSIX path, such as `/usr/lib/libc.so.6` or `../notes.txt`
+/// ```
+/// use human_regex::{beginning, end, patterns::paths::posix_path};
+/// let regex_string = beginning() + posix_path() + end();
+/// for path in ["/usr/lib/libc.so.6", "../notes.txt", "/", "src/", ".config/my app/settings.json"] {
+///     assert!(regex_string.to_regex().is_match(path));
+/// }
+/// assert!(!regex_string.to_regex().is_match("a\0b"));
+/// ```
+pub fn posix_path() -> HumanRegex<SymbolChain> {
+    posix_path_with(Naming::Unnamed)
+}
+
+/// Matches a POSIX path, capturing the `directory`, `stem` and `extension`. The `directory` is
+/// empty for a bare file name, and there is no `stem` when the path ends with a separator.
+pub fn posix_path_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    let style = PathStyle::Posix;
+    naming.capture(
+        "directory",
+        zero_or_one(separator(style)) + directories(style),
+    ) + zero_or_one(file_name(style, naming))
+}
+
+/// Matches a Windows path, such as `C:\Users\me\notes.txt`, `\\server\share\file.dll` or
+/// `..\build\out.exe`
+/// ```
+/// use human_regex::{beginning, end, patterns::paths::windows_path};
+/// let regex_string = beginning() + windows_path() + end();
+/// for path in [r"C:\Users\me\notes.txt", r"\\server\share\file.dll", r"..\build\out.exe", "D:", "C:/mixed/separators.txt"] {
+///     assert!(regex_string.to_regex().is_match(path));
+/// }
+/// assert!(!regex_string.to_regex().is_match(r"C:\what?.txt"));
+/// assert!(!regex_string.to_regex().is_match(r"C:\a\b:c"));
+/// ```
+pub fn windows_path() -> HumanRegex<SymbolChain> {
+    windows_path_with(Naming::Unnamed)
+}
+
+/// Matches a Windows path, capturing the `drive` letter or the `server` and `share` of a UNC
+/// path, along with the `directory`, `stem` and `extension`. The `directory` includes any drive or
+/// UNC prefix.
+/// ```
+/// use human_regex::{beginning, end, patterns::paths::windows_path_with, patterns::Naming};
+/// let regex_string = beginning() + windows_path_with(Naming::Named) + end();
+/// let caps = regex_string.to_regex().captures(r"C:\Program Files\App\app.exe").unwrap();
+/// assert_eq!(&caps["drive"], "C");
+/// assert_eq!(&caps["directory"], r"C:\Program Files\App\");
+/// assert_eq!(&caps["stem"], "app");
+///
+/// let caps = regex_string.to_regex().captures(r"\\fileserver\builds\nightly.zip").unwrap();
+/// assert_eq!(&caps["server"], "fileserver");
+/// assert_eq!(&caps["share"], "builds");
+/// assert!(caps.name("drive").is_none());
+/// ```
+pub fn windows_path_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    let style = PathStyle::Windows;
+    let prefix = or(&[
+        naming.capture("drive", within_set(&["A-Z", "a-z"])) + text(":"),
+        separator(style)
+            + separator(style)
+            + naming.capture("server", path_component(style))
+            + separator(style)
+            + naming.capture("share", path_component(style)),
+    ]);
+    naming.capture(
+        "directory",
+        zero_or_one(prefix) + zero_or_one(separator(style)) + directories(style),
+    ) + zero_or_one(file_name(style, naming))
+}
@@ -0,0 +1,162 @@
+//! Patterns for version numbers and the requirements that package managers write about them
+
+use super::{chain, Naming};
+use crate::humanregex::*;
+use crate::{
+    one_or_more, or, text, whitespace, within_range, within_set, zero_or_more, zero_or_one,
+};
+
+/// A number without leading zeros, as every numeric part of a version is written
+fn numeric() -> HumanRegex<SymbolChain> {
+    or(&[
+        chain(text("0")),
+        within_range('1'..='9') + zero_or_more(within_range('0'..='9')),
+    ])
+}
+
+/// A letter, digit or hyphen, the characters of pre-release and build identifiers
+fn identifier_character() -> HumanRegex<SymbolClass<Custom>> {
+    within_set(&["0-9", "A-Z", "a-z", "-"])
+}
+
+/// One or more identifiers separated by dots
+fn dotted(identifier: impl Fn() -> HumanRegex<SymbolChain>) -> HumanRegex<SymbolChain> {
+    identifier() + zero_or_more(text(".") + identifier())
+}
+
+/// The pre-release identifiers, each a number without leading zeros or containing a letter or
+/// hyphen
+fn prerelease() -> HumanRegex<SymbolChain> {
+    dotted(|| {
+        or(&[
+            zero_or_more(within_range('0'..='9'))
+                + within_set(&["A-Z", "a-z", "-"])
+                + zero_or_more(identifier_character()),
+            numeric(),
+        ])
+    })
+}
+
+/// The build metadata identifiers, which may be any letters, digits and hyphens
+fn build() -> HumanRegex<SymbolChain> {
+    dotted(|| chain(one_or_more(identifier_character())))
+}
+
+/// Matches a version number as defined by Semantic Versioning 2.0, such as `1.0.0-rc.1+build.5`
+/// ```
+/// use human_regex::{beginning, end, patterns::semver};
+/// let regex_string = beginning() + semver() + end();
+/// assert!(regex_string.to_regex().is_match("1.0.0"));
+/// assert!(regex_string.to_regex().is_match("1.0.0-alpha.1+001"));
+/// assert!(regex_string.to_regex().is_match("1.0.0-x-y-z.--"));
+/// assert!(!regex_string.to_regex().is_match("1.0"));
+/// assert!(!regex_string.to_regex().is_match("01.0.0"));
+/// assert!(!regex_string.to_regex().is_match("1.0.0-alpha.01"));
+/// ```
+pub fn semver() -> HumanRegex<SymbolChain> {
+    semver_with(Naming::Unnamed)
+}
+
+/// Matches a version number as defined by Semantic Versioning 2.0, capturing the `major`, `minor`
+/// and `patch` numbers, the `prerelease` identifiers and the `build` metadata
+/// ```
+/// use human_regex::{patterns::version::semver_with, patterns::Naming};
+/// let regex_string = semver_with(Naming::Named);
+/// let caps = regex_string.to_regex().captures("## [2.10.0-beta.2+exp.sha.5114f85] - 2024-03-01").unwrap();
+/// assert_eq!(&caps["minor"], "10");
+/// assert_eq!(&caps["prerelease"], "beta.2");
+/// assert_eq!(&caps["build"], "exp.sha.5114f85");
+/// ```
+pub fn semver_with(naming: Naming) -> HumanRegex<SymbolChain> {
+    naming.capture("major", numeric())
+        + text(".")
+        + naming.capture("minor", numeric())
+        + text(".")
+        + naming.capture("patch", numeric())
+        + zero_or_one(text("-") + naming.capture("prerelease", prerelease()))
+        + zero_or_one(text("+") + naming.capture("build", build()))
+}
+
+/// A wildcard that stands for any number
+fn wildcard() -> HumanRegex<SymbolClass<Custom>> {
+    within_set(&[r"\*", "x", "X"])
+}
+
+/// A version that may leave out trailing parts or use wildcards for them, such as `1.2` or `1.*`
+fn partial() -> HumanRegex<SymbolChain> {
+    let rest_wild = || zero_or_one(text(".") + wildcard() + zero_or_one(text(".") + wildcard()));
+    or(&[
+        semver(),
+        numeric() + text(".") + numeric() + zero_or_one(text(".") + wildcard()),
+        numeric() + rest_wild(),
+        wildcard() + rest_wild(),
+    ])
+}
+
+/// Matches a version requirement as written in a `Cargo.toml`, such as `^1.2`, `~1` or `>=1, <2`
+/// ```
+/// use human_regex::{beginning, end, patterns::cargo_version_req};
+/// let regex_string = beginning() + cargo_version_req() + end();
+/// for requirement in ["1.2.3", "^1.2", "~1", ">=1, <2", "= 0.4.0-rc.1", "1.*", "*"] {
+///     assert!(regex_string.to_regex().is_match(requirement));
+/// }
+/// assert!(!regex_string.to_regex().is_match(">=1 <2"));
+/// assert!(!regex_string.to_regex().is_match("1.*.3"));
+/// assert!(!regex_string.to_regex().is_match("^01.2"));
+/// ```
+pub fn cargo_version_req() -> HumanRegex<SymbolChain> {
+    let comparator = || {
+        zero_or_one(or(&["=", ">=", "<=", ">", "<", "~", r"\^"]))
+            + zero_or_more(whitespace())
+            + partial()
+    };
+    let separator = zero_or_more(whitespace()) + text(",") + zero_or_more(whitespace());
+    comparator() + zero_or_more(separator + comparator())
+}
+
+/// Matches a version range as written in a `package.json`, such as `^1.2 || >=3.0.0 <3.2` or
+/// `1.2.3 - 2.x`
+/// ```
+/// use human_regex::{beginning, end, patterns::version::npm_version_range};
+/// let regex_string = beginning() + npm_version_range() + end();
+/// for range in ["^1.2.3", "~1.x", ">=1.0.0 <2", "1.2.3 - 2.3", "<1.0.0 || >=2.3.1 <2.4.5 || 5.x", "v1.2.3"] {
+///     assert!(regex_string.to_regex().is_match(range));
+/// }
+/// assert!(!regex_string.to_regex().is_match(">=1, <2"));
+/// ```
+pub fn npm_version_range() -> HumanRegex<SymbolChain> {
+    // Any part may be a wildcard, and a leading `v` is allowed
+    let part = || or(&[numeric(), chain(wildcard())]);
+    let version = || {
+        zero_or_one(text("v"))
+            + part()
+            + zero_or_one(
+                text(".")
+                    + part()
+                    + zero_or_one(
+                        text(".")
+                            + part()
+                            + zero_or_one(text("-") + prerelease())
+                            + zero_or_one(text("+") + build()),
+                    ),
+            )
+    };
+    let comparator = || {
+        zero_or_one(or(&[">=", "<=", ">", "<", "=", "~", r"\^"]))
+            + zero_or_more(whitespace())
+            + version()
+    };
+    // A hyphen range is tried first, so that its lower bound is not taken for a whole range
+    let range = || {
+        or(&[
+            version()
+                + one_or_more(whitespace())
+                + text("-")
+                + one_or_more(whitespace())
+                + version(),
+            comparator() + zero_or_more(one_or_more(whitespace()) + comparator()),
+        ])
+    };
+    let separator = zero_or_more(whitespace()) + text("||") + zero_or_more(whitespace());
+    range() + zero_or_more(separator + range())
+}
@@ -0,0 +1,175 @@
+//! Functions for matching decimal integers within a numeric range
+
+use super::humanregex::*;
+use super::{at_least, exactly, or, text, within_range, zero_or_more, zero_or_one};
+use std::marker::PhantomData as pd;
+use std::ops::RangeInclusive;
+
+/// Options for [number_in_range_with]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NumberOptions {
+    /// Whether any number of extra leading zeros is allowed, as in `007`
+    pub leading_zeros: bool,
+    /// The number of digits, not counting the sign, that shorter numbers are padded to with zeros,
+    /// as in `05` for a width of two. Numbers with more digits are written as they are.
+    pub width: Option<u8>,
+    /// Whether numbers that are not negative may be written with a `+` sign
+    pub plus_sign: bool,
+}
+
+/// Matches exactly the decimal integers in a range, written without leading zeros
+/// ```
+/// use human_regex::{beginning, end, number_in_range};
+/// let regex_string = beginning() + number_in_range(0..=255) + end();
+/// assert!(regex_string.to_regex().is_match("0"));
+/// assert!(regex_string.to_regex().is_match("199"));
+/// assert!(regex_string.to_regex().is_match("255"));
+/// assert!(!regex_string.to_regex().is_match("256"));
+/// assert!(!regex_string.to_regex().is_match("007"));
+///
+/// let years = beginning() + number_in_range(1900..=2099) + end();
+/// assert!(years.to_regex().is_match("1987"));
+/// assert!(!years.to_regex().is_match("2100"));
+///
+/// let temperatures = beginning() + number_in_range(-40..=45) + end();
+/// assert!(temperatures.to_regex().is_match("-40"));
+/// assert!(!temperatures.to_regex().is_match("-41"));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+pub fn number_in_range(range: RangeInclusive<i64>) -> HumanRegex<SymbolChain> {
+    number_in_range_with(range, NumberOptions::default())
+}
+
+/// Matches exactly the decimal integers in a range, written as the [NumberOptions] describe
+/// ```
+/// use human_regex::{beginning, end, number_in_range_with, NumberOptions};
+/// let months = number_in_range_with(1..=12, NumberOptions { width: Some(2), ..Default::default() });
+/// let regex_string = beginning() + months + end();
+/// assert!(regex_string.to_regex().is_match("07"));
+/// assert!(regex_string.to_regex().is_match("12"));
+/// assert!(!regex_string.to_regex().is_match("7"));
+/// assert!(!regex_string.to_regex().is_match("00"));
+///
+/// let options = NumberOptions { leading_zeros: true, plus_sign: true, ..Default::default() };
+/// let regex_string = beginning() + number_in_range_with(-5..=5, options) + end();
+/// assert!(regex_string.to_regex().is_match("+005"));
+/// assert!(regex_string.to_regex().is_match("-05"));
+/// assert!(!regex_string.to_regex().is_match("+6"));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+pub fn number_in_range_with(
+    range: RangeInclusive<i64>,
+    options: NumberOptions,
+) -> HumanRegex<SymbolChain> {
+    let (lo, hi) = (*range.start(), *range.end());
+    assert!(lo <= hi, "the range {}..={} is empty", lo, hi);
+    let mut signed = Vec::new();
+    if lo < 0 {
+        // The magnitudes of the negative numbers, leaving zero to the other side
+        let smallest = if hi < 0 { hi.unsigned_abs() } else { 1 };
+        signed.push(text("-") + magnitudes(smallest, lo.unsigned_abs(), options));
+    }
+    if hi >= 0 {
+        let digits = magnitudes(lo.max(0) as u64, hi as u64, options);
+        signed.push(if options.plus_sign {
+            zero_or_one(text("+")) + digits
+        } else {
+            digits
+        });
+    }
+    or(&signed)
+}
+
+/// Matches the numbers from `lo` to `hi`, longest first so that an unanchored match takes as many
+/// digits as it can
+fn magnitudes(lo: u64, hi: u64, options: NumberOptions) -> HumanRegex<SymbolChain> {
+    let mut alternatives = Vec::new();
+    let (shortest, longest) = (lo.to_string().len(), hi.to_string().len());
+    for length in (shortest..=longest).rev() {
+        let first = if length == shortest {
+            lo
+        } else {
+            10u64.pow(length as u32 - 1)
+        };
+        let last = if length == longest {
+            hi
+        } else {
+            10u64.pow(length as u32) - 1
+        };
+        let padding = options
+            .width
+            .map_or(0, |width| (width as usize).saturating_sub(length));
+        let zeros = match (options.leading_zeros, padding) {
+            (true, 0) => zero_or_more(text("0")).0,
+            (true, padding) => at_least(padding as u8, text("0")).0,
+            (false, 0) => String::new(),
+            (false, padding) => exactly(padding as u8, text("0")).0,
+        };
+        for digits in same_length(first.to_string().as_bytes(), last.to_string().as_bytes()) {
+            alternatives.push(HumanRegex(
+                format!("{}{}", zeros, digits),
+                pd::<SymbolChain>,
+            ));
+        }
+    }
+    or(&alternatives)
+}
+
+/// Matches the numbers between two numbers with the same count of digits, as a list of
+/// alternatives
+fn same_length(lo: &[u8], hi: &[u8]) -> Vec<HumanRegex<SymbolChain>> {
+    let (Some(&first), Some(&last)) = (lo.first(), hi.first()) else {
+        return vec![HumanRegex(String::new(), pd::<SymbolChain>)];
+    };
+    let rest = lo.len() - 1;
+    let any_rest = || match rest {
+        0 => HumanRegex(String::new(), pd::<SymbolChain>),
+        _ => HumanRegex(
+            exactly(rest as u8, within_range('0'..='9')).0,
+            pd::<SymbolChain>,
+        ),
+    };
+    let digit = |from: u8, to: u8| {
+        if from == to {
+            HumanRegex(text((from as char).to_string()).0, pd::<SymbolChain>)
+        } else {
+            HumanRegex(within_range(from as char..=to as char).0, pd::<SymbolChain>)
+        }
+    };
+    if first == last {
+        return same_length(&lo[1..], &hi[1..])
+            .into_iter()
+            .map(|tail| digit(first, first) + tail)
+            .collect();
+    }
+    let lo_is_floor = lo[1..].iter().all(|&d| d == b'0');
+    let hi_is_ceiling = hi[1..].iter().all(|&d| d == b'9');
+    let mut alternatives = Vec::new();
+    // The numbers that start like `lo`, unless they are all covered by the middle
+    let middle_from = if lo_is_floor {
+        first
+    } else {
+        let ceiling = vec![b'9'; rest];
+        for tail in same_length(&lo[1..], &ceiling) {
+            alternatives.push(digit(first, first) + tail);
+        }
+        first + 1
+    };
+    let middle_to = if hi_is_ceiling { last } else { last - 1 };
+    if middle_from <= middle_to {
+        alternatives.push(digit(middle_from, middle_to) + any_rest());
+    }
+    if !hi_is_ceiling {
+        let floor = vec![b'0'; rest];
+        for tail in same_length(&floor, &hi[1..]) {
+            alternatives.push(digit(last, last) + tail);
+        }
+    }
+    alternatives
+}
@@ -0,0 +1,148 @@
+//! Functions for matching one record of tabular data, such as a line of a CSV, TSV or fixed-width file
+
+use super::humanregex::*;
+use super::{any, exactly, named_capture, or, text, without_set, zero_or_more};
+use std::borrow::Cow;
+use std::marker::PhantomData as pd;
+
+/// Whether a field of a [record] may be wrapped in double quotes, as RFC 4180 describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Quoting {
+    /// The field is never quoted
+    #[default]
+    Never,
+    /// The field may be quoted. The capture holds the field as written, including any quotes,
+    /// which [unquote] removes.
+    Optional,
+    /// The field is always quoted, and the capture holds only the text between the quotes
+    Always,
+}
+
+/// The name, contents and quoting of a field of a [record]
+#[derive(Debug)]
+pub struct Field<'a> {
+    /// The name of the capture group that holds the field
+    name: &'a str,
+    /// What the field contains, or any text when not given
+    pattern: Option<HumanRegex<SymbolChain>>,
+    /// Whether the field may be quoted
+    quoting: Quoting,
+}
+
+impl<'a> Field<'a> {
+    /// A field captured under the given name, which holds any text and is never quoted
+    pub fn new(name: &'a str) -> Self {
+        Field {
+            name,
+            pattern: None,
+            quoting: Quoting::Never,
+        }
+    }
+
+    /// Only match fields that the pattern matches. In a quoted field, the pattern has to match the
+    /// text between the quotes as written, with any quotes in it doubled.
+    pub fn matching<T>(self, pattern: HumanRegex<T>) -> Self {
+        Field {
+            pattern: Some(HumanRegex(pattern.0, pd::<SymbolChain>)),
+            ..self
+        }
+    }
+
+    /// Set whether the field may be quoted
+    pub fn quoted(self, quoting: Quoting) -> Self {
+        Field { quoting, ..self }
+    }
+}
+
+/// Matches one record of delimited fields, such as a line of a CSV or TSV file, capturing each
+/// field under its name
+/// ```
+/// use human_regex::{beginning, digit, end, one_or_more, record, Field, Quoting};
+/// let fields = vec![
+///     Field::new("name").quoted(Quoting::Always),
+///     Field::new("age").matching(one_or_more(digit())),
+///     Field::new("city").quoted(Quoting::Optional),
+/// ];
+/// let regex_string = beginning() + record(fields, ',') + end();
+/// let caps = regex_string.to_regex().captures(r#""Doe, Jane ""JD""",42,"New York""#).unwrap();
+/// assert_eq!(&caps["name"], r#"Doe, Jane ""JD"""#);
+/// assert_eq!(&caps["age"], "42");
+/// assert_eq!(&caps["city"], r#""New York""#);
+/// assert!(!regex_string.to_regex().is_match(r#""Doe, Jane",forty-two,New York"#));
+/// ```
+///
+/// A field without a pattern matches any text up to the next delimiter, or, when quoted, any text
+/// at all in which quotes are doubled, including delimiters and line breaks.
+pub fn record(fields: Vec<Field>, delimiter: char) -> HumanRegex<SymbolChain> {
+    let delimiter = delimiter.to_string();
+    let mut regex_string = HumanRegex(String::new(), pd::<SymbolChain>);
+    for (index, field) in fields.into_iter().enumerate() {
+        if index > 0 {
+            regex_string = regex_string + text(&delimiter);
+        }
+        let content = |quoted: bool| match (&field.pattern, quoted) {
+            (Some(pattern), _) => HumanRegex(pattern.0.clone(), pd::<SymbolChain>),
+            (None, false) => {
+                let excluded = [
+                    regex::escape(&delimiter),
+                    "\"".to_string(),
+                    r"\r\n".to_string(),
+                ];
+                HumanRegex(zero_or_more(without_set(&excluded)).0, pd::<SymbolChain>)
+            }
+            (None, true) => {
+                let character = or(&[
+                    HumanRegex(without_set(&["\""]).0, pd::<SymbolChain>),
+                    HumanRegex(text("\"\"").0, pd::<SymbolChain>),
+                ]);
+                HumanRegex(zero_or_more(character).0, pd::<SymbolChain>)
+            }
+        };
+        regex_string = regex_string
+            + match field.quoting {
+                Quoting::Never => named_capture(content(false), field.name),
+                Quoting::Optional => named_capture(
+                    or(&[text("\"") + content(true) + text("\""), content(false)]),
+                    field.name,
+                ),
+                Quoting::Always => {
+                    text("\"") + named_capture(content(true), field.name) + text("\"")
+                }
+            };
+    }
+    regex_string
+}
+
+/// Matches one record of fixed-width columns, capturing each column under its name. Columns are
+/// measured in characters and keep any padding.
+/// ```
+/// use human_regex::{beginning, end, fixed_width_record};
+/// let regex_string = beginning() + fixed_width_record(&[("id", 4), ("name", 10), ("score", 3)]) + end();
+/// let caps = regex_string.to_regex().captures("0042Ada Lovela100").unwrap();
+/// assert_eq!(&caps["name"], "Ada Lovela");
+/// assert_eq!(caps["score"].trim().parse::<u8>().unwrap(), 100);
+/// assert!(!regex_string.to_regex().is_match("0042Ada100"));
+/// ```
+pub fn fixed_width_record(columns: &[(&str, u8)]) -> HumanRegex<SymbolChain> {
+    columns.iter().fold(
+        HumanRegex(String::new(), pd::<SymbolChain>),
+        |regex_string, &(name, width)| regex_string + named_capture(exactly(width, any()), name),
+    )
+}
+
+/// Removes the double quotes around a field captured by [record] and undoubles the quotes inside
+/// it, leaving a field without quotes as it is
+/// ```
+/// use human_regex::unquote;
+/// assert_eq!(unquote(r#""Doe, Jane ""JD""""#), r#"Doe, Jane "JD""#);
+/// assert_eq!(unquote("New York"), "New York");
+/// ```
+pub fn unquote(field: &str) -> Cow<'_, str> {
+    match field
+        .strip_prefix('"')
+        .and_then(|field| field.strip_suffix('"'))
+    {
+        Some(inner) => Cow::Owned(inner.replace("\"\"", "\"")),
+        None => Cow::Borrowed(field),
+    }
+}
@@ -1,6 +1,7 @@
 //! Functions for matching repetitions
 
 use super::humanregex::*;
+use super::{or, text};
 use std::marker::PhantomData as pd;
 
 /// Match at least _n_ of a certain target
@@ -69,3 +70,40 @@ pub fn zero_or_one<T>(target: HumanRegex<T>) -> HumanRegex<Quantifier> {
 pub fn exactly<T>(n: u8, target: HumanRegex<T>) -> HumanRegex<Quantifier> {
     HumanRegex(format!("(?:{}){{{}}}", target, n), pd::<Quantifier>)
 }
+
+/// Match a pair of delimiters around any mix of _inner_ and further pairs, nested at most
+/// _max_depth_ deep. The _inner_ target should match one piece of the text between delimiters,
+/// and not the delimiters themselves.
+/// ```
+/// use human_regex::{balanced, beginning, end, without_set};
+/// let regex_string = beginning() + balanced("(", ")", 2, without_set(&[r"\(", r"\)"])) + end();
+/// assert!(regex_string.to_regex().is_match("(a (b) c (d))"));
+/// assert!(!regex_string.to_regex().is_match("(a (b (c)))"));
+/// assert!(!regex_string.to_regex().is_match("(a (b)"));
+///
+/// let arrays = balanced("[", "]", 3, without_set(&[r"\[", r"\]"]));
+/// let found: Vec<&str> = arrays.to_regex().find_iter("x = [[1, 2], [3]]; y = [4]").map(|m| m.as_str()).collect();
+/// assert_eq!(found, vec!["[[1, 2], [3]]", "[4]"]);
+/// ```
+///
+/// A _max_depth_ of 0 is treated as 1, which matches one pair with no pairs inside it.
+/// ```
+/// use human_regex::{balanced, beginning, end, without_set};
+/// let regex_string = beginning() + balanced("{", "}", 0, without_set(&[r"\{", r"\}"])) + end();
+/// assert!(regex_string.to_regex().is_match("{a b}"));
+/// assert!(!regex_string.to_regex().is_match("{a {b}}"));
+/// ```
+pub fn balanced<T>(
+    open: &str,
+    close: &str,
+    max_depth: u8,
+    inner: HumanRegex<T>,
+) -> HumanRegex<SymbolChain> {
+    let inner = HumanRegex(inner.0, pd::<SymbolChain>);
+    // Each level allows the level below it, so the pattern grows linearly with the depth
+    let mut nested = text(open) + zero_or_more(inner.clone()) + text(close);
+    for _ in 1..max_depth {
+        nested = text(open) + zero_or_more(or(&[nested, inner.clone()])) + text(close);
+    }
+    nested
+}
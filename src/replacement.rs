@@ -0,0 +1,455 @@
+//! Functions for building the text that replaces each match, instead of writing `$name` templates
+//!
+//! A [Replacement] refers to capture groups by the [HumanRegex] that made them, and is checked
+//! against the regex before it is used, so a group that is not in the regex is an error rather
+//! than an empty string. Parts of a replacement can also be changed before they are inserted, for
+//! example to uppercase or through a lookup table, which templates cannot do.
+//! ```
+//! use human_regex::{digit, exactly, group_ref, literal, named_capture, text};
+//! let year = named_capture(exactly(4, digit()), "year");
+//! let month = named_capture(exactly(2, digit()), "month");
+//! let day = named_capture(exactly(2, digit()), "day");
+//! let regex_string = &year + text("-") + &month + text("-") + &day;
+//!
+//! let replacement = group_ref(&day) + literal("/") + group_ref(&month) + literal("/") + group_ref(&year);
+//! let replaced = regex_string.replace_all("Due 2024-03-14, paid 2024-03-20", &replacement).unwrap();
+//! assert_eq!(replaced, "Due 14/03/2024, paid 20/03/2024");
+//!
+//! let months = [("03", "mar"), ("04", "apr")];
+//! let replacement = group_ref(&day) + literal(" ") + group_ref(&month).lookup(&months).to_title();
+//! assert_eq!(regex_string.replace("2024-03-14", &replacement).unwrap(), "14 Mar");
+//! ```
+
+use super::capturing::group_indices;
+use super::humanregex::*;
+use regex::{Captures, Replacer};
+use std::borrow::Cow;
+use std::ops::Add;
+
+/// The text that replaces a match, made of literal text and the text of capture groups
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Replacement(Vec<Piece>);
+
+/// A piece of a [Replacement]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    /// Text that is inserted as it is
+    Literal(String),
+    /// The text of the whole match
+    Whole,
+    /// The text of the capture group with the given regex string
+    Group(String),
+    /// The text of the inner pieces, changed by a transformation
+    Transformed(Vec<Piece>, Transform),
+}
+
+/// A change made to the text of part of a [Replacement]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Transform {
+    /// Make every letter uppercase
+    Upper,
+    /// Make every letter lowercase
+    Lower,
+    /// Make the first letter of every word uppercase and the rest lowercase
+    Title,
+    /// Remove whitespace from both ends
+    Trim,
+    /// Add the character to the start until the text is at least the width in characters
+    PadStart(usize, char),
+    /// Add the character to the end until the text is at least the width in characters
+    PadEnd(usize, char),
+    /// Replace text that equals a key with its value, leaving other text as it is
+    Lookup(Vec<(String, String)>),
+}
+
+impl Transform {
+    /// Apply the transformation to some text
+    fn apply(&self, text: &str) -> String {
+        match self {
+            Transform::Upper => text.to_uppercase(),
+            Transform::Lower => text.to_lowercase(),
+            Transform::Title => {
+                let mut titled = String::with_capacity(text.len());
+                let mut word_start = true;
+                for character in text.chars() {
+                    if word_start {
+                        titled.extend(character.to_uppercase());
+                    } else {
+                        titled.extend(character.to_lowercase());
+                    }
+                    word_start = character.is_whitespace();
+                }
+                titled
+            }
+            Transform::Trim => text.trim().to_string(),
+            Transform::PadStart(width, fill) => {
+                let padding = width.saturating_sub(text.chars().count());
+                fill.to_string().repeat(padding) + text
+            }
+            Transform::PadEnd(width, fill) => {
+                let padding = width.saturating_sub(text.chars().count());
+                text.to_string() + &fill.to_string().repeat(padding)
+            }
+            Transform::Lookup(table) => table
+                .iter()
+                .find(|(key, _)| key == text)
+                .map_or(text, |(_, value)| value)
+                .to_string(),
+        }
+    }
+}
+
+/// Text that is inserted as it is, so a `$` is just a dollar sign
+/// ```
+/// use human_regex::{literal, text};
+/// assert_eq!(text("USD").replace_all("5 USD", &literal("$")).unwrap(), "5 $");
+/// ```
+pub fn literal(text: &str) -> Replacement {
+    Replacement(vec![Piece::Literal(text.to_string())])
+}
+
+/// The text of a capture group made by [capture](crate::capture) or
+/// [named_capture](crate::named_capture), or nothing when the group did not take part in the match
+/// ```
+/// use human_regex::{capture, digit, group_ref, literal, one_or_more, text};
+/// let amount = capture(one_or_more(digit()));
+/// let regex_string = &amount + text(" USD");
+/// let replacement = literal("$") + group_ref(&amount) + literal("a");
+/// assert_eq!(regex_string.replace_all("5 USD", &replacement).unwrap(), "$5a");
+/// ```
+pub fn group_ref<T>(group: &HumanRegex<T>) -> Replacement {
+    Replacement(vec![Piece::Group(group.0.clone())])
+}
+
+/// The text of the whole match
+/// ```
+/// use human_regex::{digit, literal, one_or_more, whole_match};
+/// let replacement = literal("[") + whole_match() + literal("]");
+/// assert_eq!(one_or_more(digit()).replace_all("a1b22", &replacement).unwrap(), "a[1]b[22]");
+/// ```
+pub fn whole_match() -> Replacement {
+    Replacement(vec![Piece::Whole])
+}
+
+impl Replacement {
+    /// Wrap the whole replacement so far in a transformation
+    fn transformed(self, transform: Transform) -> Replacement {
+        Replacement(vec![Piece::Transformed(self.0, transform)])
+    }
+
+    /// Make every letter uppercase
+    /// ```
+    /// use human_regex::{group_ref, literal, named_capture, one_or_more, text, word};
+    /// let code = named_capture(one_or_more(word()), "code");
+    /// let regex_string = text("#") + &code;
+    /// let replacement = literal("#") + group_ref(&code).to_upper();
+    /// assert_eq!(regex_string.replace_all("see #abc1 and #Def2", &replacement).unwrap(), "see #ABC1 and #DEF2");
+    /// ```
+    pub fn to_upper(self) -> Replacement {
+        self.transformed(Transform::Upper)
+    }
+
+    /// Make every letter lowercase
+    /// ```
+    /// use human_regex::{one_or_more, uppercase, whole_match};
+    /// let replaced = one_or_more(uppercase()).replace_all("Stop SHOUTING", &whole_match().to_lower());
+    /// assert_eq!(replaced.unwrap(), "stop shouting");
+    /// ```
+    pub fn to_lower(self) -> Replacement {
+        self.transformed(Transform::Lower)
+    }
+
+    /// Make the first letter of every word uppercase and the rest lowercase, where words are
+    /// separated by whitespace
+    /// ```
+    /// use human_regex::{any, group_ref, named_capture, one_or_more, text};
+    /// let name = named_capture(one_or_more(any()), "name");
+    /// let regex_string = text("name: ") + &name;
+    /// let replaced = regex_string.replace("name: ada LOVELACE", &group_ref(&name).to_title());
+    /// assert_eq!(replaced.unwrap(), "Ada Lovelace");
+    /// ```
+    pub fn to_title(self) -> Replacement {
+        self.transformed(Transform::Title)
+    }
+
+    /// Remove whitespace from both ends
+    /// ```
+    /// use human_regex::{group_ref, literal, named_capture, text, without_set, zero_or_more};
+    /// let cell = named_capture(zero_or_more(without_set(&["|"])), "cell");
+    /// let regex_string = text("|") + &cell;
+    /// let replacement = literal("|") + group_ref(&cell).trim();
+    /// assert_eq!(regex_string.replace_all("|  a |b   ", &replacement).unwrap(), "|a|b");
+    /// ```
+    pub fn trim(self) -> Replacement {
+        self.transformed(Transform::Trim)
+    }
+
+    /// Add the fill character to the start until the text is at least the width in characters
+    /// ```
+    /// use human_regex::{digit, one_or_more, whole_match};
+    /// let replaced = one_or_more(digit()).replace_all("7 of 120", &whole_match().pad_start(3, '0'));
+    /// assert_eq!(replaced.unwrap(), "007 of 120");
+    /// ```
+    pub fn pad_start(self, width: usize, fill: char) -> Replacement {
+        self.transformed(Transform::PadStart(width, fill))
+    }
+
+    /// Add the fill character to the end until the text is at least the width in characters
+    /// ```
+    /// use human_regex::{literal, one_or_more, whole_match, word};
+    /// let replacement = literal("|") + whole_match().pad_end(5, ' ') + literal("|");
+    /// assert_eq!(one_or_more(word()).replace_all("ab", &replacement).unwrap(), "|ab   |");
+    /// ```
+    pub fn pad_end(self, width: usize, fill: char) -> Replacement {
+        self.transformed(Transform::PadEnd(width, fill))
+    }
+
+    /// Replace text that equals one of the keys in the table with its value, leaving other text as
+    /// it is
+    /// ```
+    /// use human_regex::{group_ref, literal, named_capture, one_or_more, text, word};
+    /// let state = named_capture(one_or_more(word()), "state");
+    /// let regex_string = text(", ") + &state;
+    /// let states = [("CA", "California"), ("NY", "New York")];
+    /// let replacement = literal(", ") + group_ref(&state).to_upper().lookup(&states);
+    /// let replaced = regex_string.replace_all("Fresno, ca; Albany, NY; Reno, NV", &replacement);
+    /// assert_eq!(replaced.unwrap(), "Fresno, California; Albany, New York; Reno, NV");
+    /// ```
+    pub fn lookup(self, table: &[(&str, &str)]) -> Replacement {
+        let table = table
+            .iter()
+            .map(|&(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        self.transformed(Transform::Lookup(table))
+    }
+}
+
+/// Replacements are joined one after the other
+impl Add for Replacement {
+    type Output = Replacement;
+
+    fn add(mut self, rhs: Replacement) -> Self::Output {
+        self.0.extend(rhs.0);
+        self
+    }
+}
+
+/// An error for a [Replacement] that does not fit the regex it is used with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplacementError {
+    /// A description of what does not fit
+    pub message: String,
+}
+
+impl fmt::Display for ReplacementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid replacement: {}", self.message)
+    }
+}
+
+impl std::error::Error for ReplacementError {}
+
+/// A piece of a [BoundReplacement], with capture groups resolved to their indices
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Resolved {
+    /// Text that is inserted as it is
+    Literal(String),
+    /// The text of the capture group with the given index, where 0 is the whole match
+    Group(usize),
+    /// The text of the inner pieces, changed by a transformation
+    Transformed(Vec<Resolved>, Transform),
+}
+
+/// Write the text of resolved pieces for one match
+fn render(pieces: &[Resolved], caps: &Captures<'_>, dst: &mut String) {
+    for piece in pieces {
+        match piece {
+            Resolved::Literal(text) => dst.push_str(text),
+            Resolved::Group(index) => {
+                dst.push_str(caps.get(*index).map_or("", |found| found.as_str()))
+            }
+            Resolved::Transformed(inner, transform) => {
+                let mut text = String::new();
+                render(inner, caps, &mut text);
+                dst.push_str(&transform.apply(&text));
+            }
+        }
+    }
+}
+
+/// Resolve the capture groups that pieces refer to
+fn resolve(
+    pieces: &[Piece],
+    groups: &[(String, usize)],
+) -> Result<Vec<Resolved>, ReplacementError> {
+    pieces
+        .iter()
+        .map(|piece| match piece {
+            Piece::Literal(text) => Ok(Resolved::Literal(text.clone())),
+            Piece::Whole => Ok(Resolved::Group(0)),
+            Piece::Group(group) => match group_indices(groups, group).as_slice() {
+                [index] => Ok(Resolved::Group(*index)),
+                [] => Err(ReplacementError {
+                    message: format!("the regex has no capture group `{}`", group),
+                }),
+                _ => Err(ReplacementError {
+                    message: format!(
+                        "the regex has more than one capture group `{}`, so the reference is ambiguous",
+                        group
+                    ),
+                }),
+            },
+            Piece::Transformed(inner, transform) => Ok(Resolved::Transformed(
+                resolve(inner, groups)?,
+                transform.clone(),
+            )),
+        })
+        .collect()
+}
+
+/// A [Replacement] bound to the capture groups of one regex, which the regex crate can use directly
+/// ```
+/// use human_regex::{group_ref, literal, named_capture, one_or_more, text, word};
+/// let name = named_capture(one_or_more(word()), "name");
+/// let regex_string = text("@") + &name;
+/// let replacer = (literal("<") + group_ref(&name) + literal(">")).bind(&regex_string).unwrap();
+/// assert_eq!(regex_string.to_regex().replace_all("hi @ada", replacer), "hi <ada>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundReplacement(Vec<Resolved>);
+
+impl Replacer for BoundReplacement {
+    fn replace_append(&mut self, caps: &Captures<'_>, dst: &mut String) {
+        self.append(caps, dst);
+    }
+
+    fn no_expansion(&mut self) -> Option<Cow<'_, str>> {
+        match self.0.as_slice() {
+            [] => Some(Cow::Borrowed("")),
+            [Resolved::Literal(text)] => Some(Cow::Borrowed(text)),
+            _ => None,
+        }
+    }
+}
+
+impl BoundReplacement {
+    /// Write the replacement for one match
+    pub(crate) fn append(&self, caps: &Captures<'_>, dst: &mut String) {
+        render(&self.0, caps, dst);
+    }
+
+    /// Refer to the same groups inside a larger regex, where the whole match is the group with
+    /// the given index and the groups inside it follow
+    pub(crate) fn shifted(&self, whole: usize) -> BoundReplacement {
+        /// Shift the group indices of resolved pieces
+        fn shift(pieces: &[Resolved], whole: usize) -> Vec<Resolved> {
+            pieces
+                .iter()
+                .map(|piece| match piece {
+                    Resolved::Literal(text) => Resolved::Literal(text.clone()),
+                    Resolved::Group(index) => Resolved::Group(whole + index),
+                    Resolved::Transformed(inner, transform) => {
+                        Resolved::Transformed(shift(inner, whole), transform.clone())
+                    }
+                })
+                .collect()
+        }
+        BoundReplacement(shift(&self.0, whole))
+    }
+}
+
+impl Replacement {
+    /// Resolve the capture groups this replacement refers to within a regex
+    /// ```
+    /// use human_regex::{capture, digit, group_ref, literal, one_or_more, text};
+    /// let first = capture(one_or_more(digit()));
+    /// let second = capture(one_or_more(digit()));
+    /// let regex_string = &first + text("-") + &second;
+    /// let error = (group_ref(&second) + literal("-") + group_ref(&first)).bind(&regex_string).unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "invalid replacement: the regex has more than one capture group `((?:\\d)+)`, so the reference is ambiguous"
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regex does not contain one of the groups, or contains it more than
+    /// once, as groups made by [capture](crate::capture) around the same target are.
+    pub fn bind<T>(&self, pattern: &HumanRegex<T>) -> Result<BoundReplacement, ReplacementError> {
+        resolve(&self.0, &pattern.capture_groups()).map(BoundReplacement)
+    }
+
+    /// Render this replacement as a template in the syntax of the regex crate, with every `$` in
+    /// literal text doubled and every group written in braces
+    /// ```
+    /// use human_regex::{capture, digit, group_ref, literal, named_capture, one_or_more, text};
+    /// let count = capture(one_or_more(digit()));
+    /// let unit = named_capture(text("USD"), "unit");
+    /// let regex_string = &count + text(" ") + &unit;
+    /// let replacement = literal("$") + group_ref(&count) + literal("a ") + group_ref(&unit);
+    /// assert_eq!(replacement.to_template(&regex_string).unwrap(), "$$${1}a ${unit}");
+    ///
+    /// let error = group_ref(&unit).to_template(&count).unwrap_err();
+    /// assert_eq!(error.to_string(), "invalid replacement: the regex has no capture group `(?P<unit>(?:USD))`");
+    /// assert!(group_ref(&unit).to_lower().to_template(&regex_string).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regex does not contain one of the groups or contains it more than
+    /// once, or if the replacement transforms any text, which templates cannot do.
+    pub fn to_template<T>(&self, pattern: &HumanRegex<T>) -> Result<String, ReplacementError> {
+        let BoundReplacement(pieces) = self.bind(pattern)?;
+        let names: Vec<Option<String>> = pattern
+            .to_regex()
+            .capture_names()
+            .map(|name| name.map(str::to_string))
+            .collect();
+        pieces
+            .iter()
+            .map(|piece| match piece {
+                Resolved::Literal(text) => Ok(text.replace('$', "$$")),
+                Resolved::Group(index) => Ok(match &names[*index] {
+                    Some(name) => format!("${{{}}}", name),
+                    None => format!("${{{}}}", index),
+                }),
+                Resolved::Transformed(..) => Err(ReplacementError {
+                    message: "a template cannot transform the text of a group".to_string(),
+                }),
+            })
+            .collect()
+    }
+}
+
+impl<T> HumanRegex<T> {
+    /// Replace the leftmost match in the haystack
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the replacement refers to a group that is not in this regex, or is in it
+    /// more than once.
+    pub fn replace<'h>(
+        &self,
+        haystack: &'h str,
+        replacement: &Replacement,
+    ) -> Result<Cow<'h, str>, ReplacementError> {
+        Ok(self.to_regex().replace(haystack, replacement.bind(self)?))
+    }
+
+    /// Replace every non-overlapping match in the haystack
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the replacement refers to a group that is not in this regex, or is in it
+    /// more than once.
+    pub fn replace_all<'h>(
+        &self,
+        haystack: &'h str,
+        replacement: &Replacement,
+    ) -> Result<Cow<'h, str>, ReplacementError> {
+        Ok(self
+            .to_regex()
+            .replace_all(haystack, replacement.bind(self)?))
+    }
+}
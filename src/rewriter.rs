@@ -0,0 +1,196 @@
+//! Functions for applying several replacement rules in a single pass over the text
+//!
+//! The rules are joined into one alternation, so text that one rule replaces is never looked at by
+//! another, and when two rules match at the same position the one added first is used.
+//! ```
+//! use human_regex::{literal, one_or_more, text, whitespace, Rewriter};
+//! let rewriter = Rewriter::new()
+//!     .rule(text("cat"), literal("dog"))
+//!     .rule(text("dog"), literal("cat"))
+//!     .rule(one_or_more(whitespace()), literal(" "));
+//! assert_eq!(rewriter.rewrite("cat  chases\tdog").unwrap(), "dog chases cat");
+//! ```
+
+use super::dsl::describe_invalid;
+use super::humanregex::*;
+use super::replacement::{BoundReplacement, Replacement, ReplacementError};
+use regex::{Captures, Regex, Replacer};
+use regex_syntax::ast::{self, Ast, GroupKind};
+use std::borrow::Cow;
+
+/// An ordered list of rules, each a regex and the [Replacement] for its matches
+#[derive(Debug, Clone, Default)]
+pub struct Rewriter {
+    /// The regex string and replacement of every rule, in order
+    rules: Vec<(String, Replacement)>,
+}
+
+impl Rewriter {
+    /// A rewriter without any rules, which leaves text as it is
+    pub fn new() -> Self {
+        Rewriter { rules: Vec::new() }
+    }
+
+    /// Add a rule, which is used after the rules added before it. Rules may use the same names for
+    /// their capture groups.
+    /// ```
+    /// use human_regex::{digit, exactly, group_ref, literal, named_capture, text, Rewriter};
+    /// let two_digits = || exactly(2, digit());
+    /// let (day, month) = (named_capture(two_digits(), "day"), named_capture(two_digits(), "month"));
+    /// let (us_day, us_month) = (named_capture(two_digits(), "day"), named_capture(two_digits(), "month"));
+    /// let iso = |day, month| group_ref(month) + literal("-") + group_ref(day);
+    /// let rewriter = Rewriter::new()
+    ///     .rule(&day + text(".") + &month, iso(&day, &month))
+    ///     .rule(&us_month + text("/") + &us_day, iso(&us_day, &us_month));
+    /// assert_eq!(rewriter.rewrite("14.03 or 03/15").unwrap(), "03-14 or 03-15");
+    /// ```
+    pub fn rule<T>(mut self, pattern: HumanRegex<T>, replacement: Replacement) -> Self {
+        self.rules.push((pattern.0, replacement));
+        self
+    }
+
+    /// Resolve the capture groups of every rule and join the rules into one regex
+    /// ```
+    /// use human_regex::{capture, digit, group_ref, literal, one_or_more, text, within_range, Rewriter};
+    /// let number = capture(one_or_more(digit()));
+    /// let rewriter = Rewriter::new().rule(text("#") + &number, literal("no. ") + group_ref(&number));
+    /// let bound = rewriter.bind().unwrap();
+    /// assert_eq!(bound.rewrite("#4 and #12"), "no. 4 and no. 12");
+    ///
+    /// let error = Rewriter::new().rule(text("#"), group_ref(&number)).bind().unwrap_err();
+    /// assert_eq!(error.to_string(), "invalid replacement: rule 1: the regex has no capture group `((?:\\d)+)`");
+    ///
+    /// let error = Rewriter::new()
+    ///     .rule(text("#"), literal("no. "))
+    ///     .rule(within_range('z'..='a'), literal("?"))
+    ///     .bind()
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "invalid replacement: rule 2: not a valid regex: invalid character class range, the start must be <= the end"
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regex of a rule is not valid, if the replacement of a rule refers to
+    /// a group that is not in its regex or is in it more than once, or if the rules joined together
+    /// are too big to compile.
+    pub fn bind(&self) -> Result<BoundRewriter, ReplacementError> {
+        let mut alternatives = Vec::with_capacity(self.rules.len());
+        let mut replacements = Vec::with_capacity(self.rules.len());
+        let mut whole = 1;
+        for (number, (pattern, replacement)) in self.rules.iter().enumerate() {
+            let rule_error = |message| ReplacementError {
+                message: format!("rule {}: {}", number + 1, message),
+            };
+            let compiled = Regex::new(pattern).map_err(|error| {
+                rule_error(format!(
+                    "not a valid regex: {}",
+                    describe_invalid(pattern, error)
+                ))
+            })?;
+            let rule = HumanRegex(pattern.clone(), std::marker::PhantomData::<SymbolChain>);
+            let bound = replacement
+                .bind(&rule)
+                .map_err(|error| rule_error(error.message))?;
+            replacements.push((whole, bound.shifted(whole)));
+            let unnamed = without_names(pattern)
+                .map_err(|error| rule_error(format!("not a valid regex: {}", error)))?;
+            alternatives.push(format!("({})", unnamed));
+            whole += compiled.captures_len();
+        }
+        let joined = alternatives.join("|");
+        let regex = Regex::new(&joined).map_err(|error| ReplacementError {
+            message: format!(
+                "the rules together are not a valid regex: {}",
+                describe_invalid(&joined, error)
+            ),
+        })?;
+        Ok(BoundRewriter {
+            regex,
+            rules: replacements,
+        })
+    }
+
+    /// Apply every rule in a single pass over the haystack
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regex of a rule is not valid, if the replacement of a rule refers to
+    /// a group that is not in its regex or is in it more than once, or if the rules joined together
+    /// are too big to compile.
+    pub fn rewrite<'h>(&self, haystack: &'h str) -> Result<Cow<'h, str>, ReplacementError> {
+        Ok(self.bind()?.rewrite(haystack))
+    }
+}
+
+/// A [Rewriter] with its rules joined into one regex, which can be used many times
+#[derive(Debug, Clone)]
+pub struct BoundRewriter {
+    /// The alternation of every rule, each inside a capture group
+    regex: Regex,
+    /// The index of the capture group around every rule, with its replacement
+    rules: Vec<(usize, BoundReplacement)>,
+}
+
+impl BoundRewriter {
+    /// Apply every rule in a single pass over the haystack
+    pub fn rewrite<'h>(&self, haystack: &'h str) -> Cow<'h, str> {
+        if self.rules.is_empty() {
+            return Cow::Borrowed(haystack);
+        }
+        self.regex.replace_all(haystack, self)
+    }
+}
+
+impl Replacer for &BoundRewriter {
+    fn replace_append(&mut self, caps: &Captures<'_>, dst: &mut String) {
+        if let Some((_, replacement)) = self
+            .rules
+            .iter()
+            .find(|(whole, _)| caps.get(*whole).is_some())
+        {
+            replacement.append(caps, dst);
+        }
+    }
+}
+
+/// The regex string with every named capture group made a numbered one, so that rules can use the
+/// same names without clashing, or why the regex string does not parse
+fn without_names(pattern: &str) -> Result<String, ast::ErrorKind> {
+    /// Find the text between the opening parenthesis and the end of the name of every named group
+    fn collect_names(node: &Ast, names: &mut Vec<(usize, usize)>) {
+        match node {
+            Ast::Group(group) => {
+                if let GroupKind::CaptureName { name, .. } = &group.kind {
+                    names.push((group.span.start.offset + 1, name.span.end.offset + 1));
+                }
+                collect_names(&group.ast, names);
+            }
+            Ast::Repetition(repetition) => collect_names(&repetition.ast, names),
+            Ast::Alternation(alternation) => alternation
+                .asts
+                .iter()
+                .for_each(|option| collect_names(option, names)),
+            Ast::Concat(concat) => concat
+                .asts
+                .iter()
+                .for_each(|part| collect_names(part, names)),
+            _ => {}
+        }
+    }
+    let parsed = ast::parse::Parser::new()
+        .parse(pattern)
+        .map_err(|error| error.kind().clone())?;
+    let mut names = Vec::new();
+    collect_names(&parsed, &mut names);
+    let mut unnamed = String::with_capacity(pattern.len());
+    let mut copied = 0;
+    for (start, end) in names {
+        unnamed.push_str(&pattern[copied..start]);
+        copied = end;
+    }
+    unnamed.push_str(&pattern[copied..]);
+    Ok(unnamed)
+}
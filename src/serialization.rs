@@ -0,0 +1,107 @@
+//! Serialization of [HumanRegex] with serde, enabled by the `serde` feature
+
+use super::humanregex::*;
+use super::tree::Node;
+use serde::de::Error;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::marker::PhantomData as pd;
+
+/// Serializes both the raw regex string and the tree of builder calls that produces an equivalent
+/// regex.
+/// ```
+/// use human_regex::{digit, exactly, text};
+/// let regex_string = exactly(4, digit()) + text("-");
+/// assert_eq!(
+///     serde_json::to_string(&regex_string).unwrap(),
+///     concat!(
+///         r#"{"regex":"(?:\\d){4}(?:\\-)","#,
+///         r#""tree":{"call":"concat","args":[{"call":"exactly","args":[4,{"call":"digit"}]},{"call":"text","args":["-"]}]}}"#
+///     )
+/// );
+/// ```
+impl<T> Serialize for HumanRegex<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("HumanRegex", 2)?;
+        state.serialize_field("regex", &self.0)?;
+        state.serialize_field("tree", &Node::from(self))?;
+        state.end()
+    }
+}
+
+/// The accepted serialized forms of a [HumanRegex]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Serialized {
+    /// Just the raw regex string
+    Raw(String),
+    /// A map with the raw regex string, the tree of builder calls, or both
+    Structured {
+        /// The raw regex string
+        regex: Option<String>,
+        /// The tree of builder calls
+        tree: Option<Node>,
+    },
+}
+
+/// Deserializes either a raw regex string or a map with a `regex` string and/or a `tree` of builder
+/// calls, validating whichever is given. When both are present, the regex string is kept as it is,
+/// and the tree has to build into an equivalent regex.
+/// ```
+/// use human_regex::{number_in_range, within_set, HumanRegex};
+/// let from_raw: HumanRegex = serde_json::from_str(r#""\\d{4}""#).unwrap();
+/// let from_tree: HumanRegex = serde_json::from_str(
+///     r#"{"tree": {"call": "exactly", "args": [4, {"call": "digit"}]}}"#
+/// ).unwrap();
+/// assert!(from_raw.to_regex().is_match("2014"));
+/// assert!(from_tree.to_regex().is_match("2014"));
+///
+/// let set = within_set(&["a-z", "_"]);
+/// let back: HumanRegex = serde_json::from_str(&serde_json::to_string(&set).unwrap()).unwrap();
+/// assert_eq!(back.to_string(), set.to_string());
+/// let range = number_in_range(0..=255);
+/// let back: HumanRegex = serde_json::from_str(&serde_json::to_string(&range).unwrap()).unwrap();
+/// assert_eq!(back.to_string(), range.to_string());
+///
+/// let invalid = serde_json::from_str::<HumanRegex>(r#"{"tree": {"call": "exactly", "args": [4]}}"#);
+/// assert!(invalid.unwrap_err().to_string().contains("`exactly` does not take 1 argument"));
+///
+/// let mismatched = serde_json::from_str::<HumanRegex>(r#"{"regex": "a", "tree": {"call": "digit"}}"#);
+/// assert!(mismatched.unwrap_err().to_string().contains("the `tree` does not build the `regex`"));
+/// ```
+impl<'de> Deserialize<'de> for HumanRegex<SymbolChain> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (regex_string, tree) = match Serialized::deserialize(deserializer)? {
+            Serialized::Raw(regex_string) => (regex_string, None),
+            Serialized::Structured {
+                regex: Some(regex_string),
+                tree,
+            } => (regex_string, tree),
+            Serialized::Structured {
+                regex: None,
+                tree: Some(tree),
+            } => (tree.build().map_err(D::Error::custom)?.0, None),
+            Serialized::Structured { .. } => {
+                return Err(D::Error::custom("expected a `regex` or a `tree`"))
+            }
+        };
+        regex::Regex::new(&regex_string).map_err(D::Error::custom)?;
+        if let Some(tree) = tree {
+            let built = tree.build().map_err(D::Error::custom)?;
+            if !equivalent(&regex_string, &built.0) {
+                return Err(D::Error::custom(format!(
+                    "the `tree` does not build the `regex`: it builds `{}`",
+                    built
+                )));
+            }
+        }
+        Ok(HumanRegex(regex_string, pd::<SymbolChain>))
+    }
+}
+
+/// Whether two regex strings parse to the same expression, ignoring differences in how they are
+/// written such as extra non-capturing groups
+fn equivalent(lhs: &str, rhs: &str) -> bool {
+    let parse = |regex_string| regex_syntax::Parser::new().parse(regex_string).ok();
+    matches!((parse(lhs), parse(rhs)), (Some(lhs), Some(rhs)) if lhs == rhs)
+}
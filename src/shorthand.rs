@@ -112,7 +112,7 @@ where
     HumanRegex(
         format!(
             "[{}]",
-            set.into_iter().map(|c| c.to_string()).collect::<String>()
+            set.iter().map(|c| c.to_string()).collect::<String>()
         ),
         pd::<SymbolClass<Custom>>,
     )
@@ -133,7 +133,7 @@ where
     HumanRegex(
         format!(
             "[^{}]",
-            set.into_iter().map(|c| c.to_string()).collect::<String>()
+            set.iter().map(|c| c.to_string()).collect::<String>()
         ),
         pd::<SymbolClass<Custom>>,
     )
@@ -143,6 +143,7 @@ where
 ///
 /// Used in the [unicode_category] function.
 #[allow(missing_docs)] // variants are self documenting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UnicodeCategory {
     Letter,
     LowercaseLetter,
@@ -182,6 +183,63 @@ pub enum UnicodeCategory {
     PrivateUse,
     Surrogate,
     Unassigned,
+    /// Characters that can start an identifier, the `XID_Start` property of Unicode Standard Annex #31
+    IdentifierStart,
+    /// Characters that can continue an identifier, the `XID_Continue` property of Unicode Standard
+    /// Annex #31
+    IdentifierContinue,
+}
+
+/// Parses a variant from its name, e.g. `"LowercaseLetter"`. Used when patterns are written as text,
+/// such as in the [dsl](crate::dsl) module.
+impl std::str::FromStr for UnicodeCategory {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "Letter" => Ok(UnicodeCategory::Letter),
+            "LowercaseLetter" => Ok(UnicodeCategory::LowercaseLetter),
+            "UppercaseLetter" => Ok(UnicodeCategory::UppercaseLetter),
+            "TitlecaseLetter" => Ok(UnicodeCategory::TitlecaseLetter),
+            "CasedLetter" => Ok(UnicodeCategory::CasedLetter),
+            "ModifierLetter" => Ok(UnicodeCategory::ModifierLetter),
+            "OtherLetter" => Ok(UnicodeCategory::OtherLetter),
+            "Mark" => Ok(UnicodeCategory::Mark),
+            "NonSpacingMark" => Ok(UnicodeCategory::NonSpacingMark),
+            "SpaceCombiningMark" => Ok(UnicodeCategory::SpaceCombiningMark),
+            "EnclosingMark" => Ok(UnicodeCategory::EnclosingMark),
+            "Separator" => Ok(UnicodeCategory::Separator),
+            "SpaceSeparator" => Ok(UnicodeCategory::SpaceSeparator),
+            "LineSeparator" => Ok(UnicodeCategory::LineSeparator),
+            "ParagraphSeparator" => Ok(UnicodeCategory::ParagraphSeparator),
+            "Symbol" => Ok(UnicodeCategory::Symbol),
+            "MathSymbol" => Ok(UnicodeCategory::MathSymbol),
+            "CurrencySymbol" => Ok(UnicodeCategory::CurrencySymbol),
+            "ModifierSymbol" => Ok(UnicodeCategory::ModifierSymbol),
+            "OtherSymbol" => Ok(UnicodeCategory::OtherSymbol),
+            "Number" => Ok(UnicodeCategory::Number),
+            "DecimalDigitNumber" => Ok(UnicodeCategory::DecimalDigitNumber),
+            "LetterNumber" => Ok(UnicodeCategory::LetterNumber),
+            "OtherNumber" => Ok(UnicodeCategory::OtherNumber),
+            "Punctuation" => Ok(UnicodeCategory::Punctuation),
+            "DashPunctuation" => Ok(UnicodeCategory::DashPunctuation),
+            "OpenPunctuation" => Ok(UnicodeCategory::OpenPunctuation),
+            "ClosePunctuation" => Ok(UnicodeCategory::ClosePunctuation),
+            "InitialPunctuation" => Ok(UnicodeCategory::InitialPunctuation),
+            "FinalPunctuation" => Ok(UnicodeCategory::FinalPunctuation),
+            "ConnectorPunctuation" => Ok(UnicodeCategory::ConnectorPunctuation),
+            "OtherPunctuation" => Ok(UnicodeCategory::OtherPunctuation),
+            "Other" => Ok(UnicodeCategory::Other),
+            "Control" => Ok(UnicodeCategory::Control),
+            "Format" => Ok(UnicodeCategory::Format),
+            "PrivateUse" => Ok(UnicodeCategory::PrivateUse),
+            "Surrogate" => Ok(UnicodeCategory::Surrogate),
+            "Unassigned" => Ok(UnicodeCategory::Unassigned),
+            "IdentifierStart" => Ok(UnicodeCategory::IdentifierStart),
+            "IdentifierContinue" => Ok(UnicodeCategory::IdentifierContinue),
+            _ => Err(format!("unknown Unicode category `{}`", name)),
+        }
+    }
 }
 
 /// A function for matching Unicode character categories. For matching script categories see [unicode_script].
@@ -234,6 +292,8 @@ pub fn unicode_category(category: UnicodeCategory) -> HumanRegex<SymbolClass<Sta
             UnicodeCategory::PrivateUse => r"\p{Private_Use}".to_string(),
             UnicodeCategory::Surrogate => r"\p{Surrogate}".to_string(),
             UnicodeCategory::Unassigned => r"\p{Unassigned}".to_string(),
+            UnicodeCategory::IdentifierStart => r"\p{XID_Start}".to_string(),
+            UnicodeCategory::IdentifierContinue => r"\p{XID_Continue}".to_string(),
         },
         pd::<SymbolClass<Standard>>,
     )
@@ -254,6 +314,7 @@ pub fn non_unicode_category(category: UnicodeCategory) -> HumanRegex<SymbolClass
 ///
 /// Used in the [unicode_script] function
 #[allow(missing_docs)] // variants are self documenting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UnicodeScript {
     Common,
     Arabic,
@@ -302,6 +363,63 @@ pub enum UnicodeScript {
     Yi,
 }
 
+/// Parses a variant from its name, e.g. `"Arabic"`. Used when patterns are written as text,
+/// such as in the [dsl](crate::dsl) module.
+impl std::str::FromStr for UnicodeScript {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "Common" => Ok(UnicodeScript::Common),
+            "Arabic" => Ok(UnicodeScript::Arabic),
+            "Armenian" => Ok(UnicodeScript::Armenian),
+            "Bengali" => Ok(UnicodeScript::Bengali),
+            "Bopomofo" => Ok(UnicodeScript::Bopomofo),
+            "Braille" => Ok(UnicodeScript::Braille),
+            "Buhid" => Ok(UnicodeScript::Buhid),
+            "CandianAboriginal" => Ok(UnicodeScript::CandianAboriginal),
+            "Cherokee" => Ok(UnicodeScript::Cherokee),
+            "Cyrillic" => Ok(UnicodeScript::Cyrillic),
+            "Devanagari" => Ok(UnicodeScript::Devanagari),
+            "Ethiopic" => Ok(UnicodeScript::Ethiopic),
+            "Georgian" => Ok(UnicodeScript::Georgian),
+            "Greek" => Ok(UnicodeScript::Greek),
+            "Gujarati" => Ok(UnicodeScript::Gujarati),
+            "Gurkmukhi" => Ok(UnicodeScript::Gurkmukhi),
+            "Han" => Ok(UnicodeScript::Han),
+            "Hangul" => Ok(UnicodeScript::Hangul),
+            "Hanunoo" => Ok(UnicodeScript::Hanunoo),
+            "Hebrew" => Ok(UnicodeScript::Hebrew),
+            "Hirigana" => Ok(UnicodeScript::Hirigana),
+            "Inherited" => Ok(UnicodeScript::Inherited),
+            "Kannada" => Ok(UnicodeScript::Kannada),
+            "Katakana" => Ok(UnicodeScript::Katakana),
+            "Khmer" => Ok(UnicodeScript::Khmer),
+            "Lao" => Ok(UnicodeScript::Lao),
+            "Latin" => Ok(UnicodeScript::Latin),
+            "Limbu" => Ok(UnicodeScript::Limbu),
+            "Malayalam" => Ok(UnicodeScript::Malayalam),
+            "Mongolian" => Ok(UnicodeScript::Mongolian),
+            "Myanmar" => Ok(UnicodeScript::Myanmar),
+            "Ogham" => Ok(UnicodeScript::Ogham),
+            "Oriya" => Ok(UnicodeScript::Oriya),
+            "Runic" => Ok(UnicodeScript::Runic),
+            "Sinhala" => Ok(UnicodeScript::Sinhala),
+            "Syriac" => Ok(UnicodeScript::Syriac),
+            "Tagalog" => Ok(UnicodeScript::Tagalog),
+            "Tagbanwa" => Ok(UnicodeScript::Tagbanwa),
+            "TaiLe" => Ok(UnicodeScript::TaiLe),
+            "Tamil" => Ok(UnicodeScript::Tamil),
+            "Telugu" => Ok(UnicodeScript::Telugu),
+            "Thaana" => Ok(UnicodeScript::Thaana),
+            "Thai" => Ok(UnicodeScript::Thai),
+            "Tibetan" => Ok(UnicodeScript::Tibetan),
+            "Yi" => Ok(UnicodeScript::Yi),
+            _ => Err(format!("unknown Unicode script `{}`", name)),
+        }
+    }
+}
+
 /// A function for matching Unicode characters belonging to a certain script category. For matching other categories see [unicode_category].
 /// ```
 /// use human_regex::{beginning, end, one_or_more, unicode_script, UnicodeScript};
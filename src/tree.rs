@@ -0,0 +1,498 @@
+//! A structured form of patterns as a tree of builder calls
+//!
+//! Any [HumanRegex] can be taken apart into a [Node] tree that names the builders that make it up.
+//! A tree prints in the syntax of the [dsl](crate::dsl) module and can be built back into a regex.
+//! The rebuilt regex matches the same text and has the same capture groups, but it is not always
+//! the same regex string: parts of the original that no builder produces, such as the `[a-z_]` of
+//! [within_set](crate::within_set), come back wrapped in a non-capturing group.
+//! ```
+//! use human_regex::{beginning, digit, exactly, text, within_set, tree::Node};
+//! let regex_string = beginning() + exactly(4, digit()) + text("-");
+//! let tree = Node::from(&regex_string);
+//! assert_eq!(tree.to_string(), "beginning + exactly(4, digit) + text('-')");
+//! assert_eq!(tree.build().unwrap().to_string(), regex_string.to_string());
+//!
+//! let tree = Node::from(&within_set(&["a-z", "_"]));
+//! assert_eq!(tree.build().unwrap().to_string(), "(?:[a-z_])");
+//! ```
+
+use super::dsl::{apply, Value, NULLARY};
+use super::humanregex::*;
+use super::shorthand::{UnicodeCategory, UnicodeScript};
+use regex_syntax::ast::{
+    self, AssertionKind, Ast, ClassAsciiKind, ClassBracketed, ClassPerlKind, ClassSet,
+    ClassSetBinaryOpKind, ClassSetItem, ClassUnicodeKind, Flag, FlagsItemKind, GroupKind,
+    RepetitionKind, RepetitionRange,
+};
+use std::marker::PhantomData as pd;
+
+/// A node in a tree of builder calls
+///
+/// Operators are represented by the calls `concat` (for `+`), `and` (for `&`), `not` (for `!`)
+/// and `lazy` (for `.lazy()`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged)
+)]
+pub enum Node {
+    /// A call to a builder, such as `exactly(4, digit)`
+    Call {
+        /// The name of the builder
+        call: String,
+        /// The arguments to the builder, if any
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        args: Vec<Node>,
+    },
+    /// A character range, such as `'a'..='z'`
+    Range {
+        /// The first and last characters of the range
+        range: (char, char),
+    },
+    /// A count, such as the `4` in `exactly(4, digit)`
+    Int(u64),
+    /// A string, such as the argument to `text`, a capture name or a Unicode category name
+    Str(String),
+    /// A list, such as the options given to `or`
+    List(Vec<Node>),
+}
+
+impl Node {
+    /// Create a call to a builder
+    /// ```
+    /// use human_regex::tree::Node;
+    /// let tree = Node::call("one_or_more", vec![Node::call("digit", vec![])]);
+    /// assert_eq!(tree.to_string(), "one_or_more(digit)");
+    /// ```
+    pub fn call(name: &str, args: Vec<Node>) -> Node {
+        Node::Call {
+            call: name.to_string(),
+            args,
+        }
+    }
+
+    /// Build the tree back into a [HumanRegex], checking that every builder exists and gets
+    /// arguments it accepts
+    /// ```
+    /// use human_regex::tree::Node;
+    /// let tree = Node::call("exactly", vec![Node::Int(2), Node::call("digit", vec![])]);
+    /// assert!(tree.build().unwrap().to_regex().is_match("42"));
+    ///
+    /// let tree = Node::call("exactly", vec![Node::Int(2)]);
+    /// assert_eq!(tree.build().unwrap_err(), "`exactly` does not take 1 argument");
+    /// ```
+    pub fn build(&self) -> Result<HumanRegex, String> {
+        match self.value()? {
+            Value::Regex(regex_string, _) => Ok(HumanRegex(regex_string, pd::<SymbolChain>)),
+            other => Err(format!("expected a pattern, found {}", other)),
+        }
+    }
+
+    /// Evaluate the tree into a dynamically typed value
+    pub(crate) fn value(&self) -> Result<Value, String> {
+        Ok(match self {
+            Node::Call { call, args } => apply(
+                call,
+                args.iter().map(Node::value).collect::<Result<_, _>>()?,
+            )?,
+            Node::Range { range } => Value::Range(range.0, range.1),
+            Node::Int(int) => Value::Int(*int),
+            Node::Str(string) => Value::Str(string.clone()),
+            Node::List(items) => {
+                Value::List(items.iter().map(Node::value).collect::<Result<_, _>>()?)
+            }
+        })
+    }
+}
+
+/// Prints the tree in the syntax of the [dsl](crate::dsl) module
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Call { call, args } => match (call.as_str(), args.as_slice()) {
+                (name, []) if NULLARY.contains(&name) => write!(f, "{}", name),
+                ("concat", [_, _, ..]) => {
+                    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                    write!(f, "{}", args.join(" + "))
+                }
+                ("lazy", [target]) => write!(f, "{}.lazy()", Operand(target)),
+                ("not", [target]) => write!(f, "!{}", Operand(target)),
+                (
+                    "unicode_category"
+                    | "non_unicode_category"
+                    | "unicode_script"
+                    | "non_unicode_script",
+                    [Node::Str(name)],
+                ) if name.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                    write!(f, "{}({})", call, name)
+                }
+                _ => {
+                    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                    write!(f, "{}({})", call, args.join(", "))
+                }
+            },
+            Node::Range { range } => write!(
+                f,
+                "{}..={}",
+                Quoted(&range.0.to_string()),
+                Quoted(&range.1.to_string())
+            ),
+            Node::Int(int) => write!(f, "{}", int),
+            Node::Str(string) => write!(f, "{}", Quoted(string)),
+            Node::List(items) => {
+                let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+        }
+    }
+}
+
+/// Prints a node in parentheses if it is a concatenation, for use next to unary operators
+struct Operand<'a>(&'a Node);
+
+impl fmt::Display for Operand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Node::Call { call, args } if call == "concat" && args.len() > 1 => {
+                write!(f, "({})", self.0)
+            }
+            node => write!(f, "{}", node),
+        }
+    }
+}
+
+/// Prints a string escaped so that the [dsl](crate::dsl) reads it back, in single quotes if it is a
+/// single character and in double quotes otherwise
+struct Quoted<'a>(&'a str);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let quote = if self.0.chars().count() == 1 {
+            '\''
+        } else {
+            '"'
+        };
+        write!(f, "{}", quote)?;
+        for c in self.0.chars() {
+            match c {
+                '\n' => write!(f, "\\n")?,
+                '\t' => write!(f, "\\t")?,
+                '\r' => write!(f, "\\r")?,
+                '\\' => write!(f, "\\\\")?,
+                _ if c == quote => write!(f, "\\{}", c)?,
+                _ if c.is_control() => write!(f, "\\u{{{:x}}}", c as u32)?,
+                _ => write!(f, "{}", c)?,
+            }
+        }
+        write!(f, "{}", quote)
+    }
+}
+
+/// Takes a regex apart into builder calls. Parts that no builder produces are kept as
+/// `nonescaped_text`.
+/// ```
+/// use human_regex::{nonescaped_text, tree::Node};
+/// let tree = Node::from(&nonescaped_text(r"\d+-x{2}"));
+/// assert_eq!(tree.to_string(), "one_or_more(digit) + text('-') + exactly(2, text('x'))");
+/// ```
+impl<T> From<&HumanRegex<T>> for Node {
+    fn from(hr: &HumanRegex<T>) -> Self {
+        match ast::parse::Parser::new().parse(&hr.0) {
+            Ok(parsed) => Decompiler(&hr.0).node(&parsed),
+            Err(_) => raw(&hr.0),
+        }
+    }
+}
+
+/// Keep a piece of regex as it is
+fn raw(regex_string: &str) -> Node {
+    Node::call("nonescaped_text", vec![Node::Str(regex_string.to_string())])
+}
+
+/// Shorthand for a call without arguments
+fn nullary(name: &str) -> Node {
+    Node::call(name, vec![])
+}
+
+/// Converts a parsed regex into builder calls, with the original pattern at hand for fallbacks
+struct Decompiler<'a>(&'a str);
+
+impl Decompiler<'_> {
+    /// The original text of a span
+    fn text(&self, span: &ast::Span) -> &str {
+        &self.0[span.start.offset..span.end.offset]
+    }
+
+    /// Convert any expression
+    fn node(&self, parsed: &Ast) -> Node {
+        match parsed {
+            Ast::Empty(_) => Node::call("concat", vec![]),
+            Ast::Literal(literal) => Node::call("text", vec![Node::Str(literal.c.to_string())]),
+            Ast::Dot(_) => nullary("any"),
+            Ast::Assertion(assertion) => match assertion.kind {
+                AssertionKind::StartLine => nullary("beginning"),
+                AssertionKind::EndLine => nullary("end"),
+                AssertionKind::StartText => nullary("beginning_of_text"),
+                AssertionKind::EndText => nullary("end_of_text"),
+                AssertionKind::WordBoundary => nullary("word_boundary"),
+                AssertionKind::NotWordBoundary => nullary("non_word_boundary"),
+                _ => raw(self.text(&assertion.span)),
+            },
+            Ast::ClassPerl(class) => perl(&class.kind, class.negated),
+            Ast::ClassUnicode(class) => {
+                unicode(class).unwrap_or_else(|| raw(self.text(&class.span)))
+            }
+            Ast::ClassBracketed(class) => {
+                bracketed(class).unwrap_or_else(|| raw(self.text(&class.span)))
+            }
+            Ast::Repetition(repetition) => {
+                let target = match &*repetition.ast {
+                    Ast::Group(group) if is_plain(&group.kind) => self.node(&group.ast),
+                    other => self.node(other),
+                };
+                let quantifier = match &repetition.op.kind {
+                    RepetitionKind::ZeroOrOne => Node::call("zero_or_one", vec![target]),
+                    RepetitionKind::ZeroOrMore => Node::call("zero_or_more", vec![target]),
+                    RepetitionKind::OneOrMore => Node::call("one_or_more", vec![target]),
+                    RepetitionKind::Range(range) => match *range {
+                        RepetitionRange::Exactly(n) if n <= 255 => {
+                            Node::call("exactly", vec![Node::Int(n as u64), target])
+                        }
+                        RepetitionRange::AtLeast(n) if n <= 255 => {
+                            Node::call("at_least", vec![Node::Int(n as u64), target])
+                        }
+                        RepetitionRange::Bounded(n, m) if m <= 255 => Node::call(
+                            "between",
+                            vec![Node::Int(n as u64), Node::Int(m as u64), target],
+                        ),
+                        _ => return raw(self.text(&repetition.span)),
+                    },
+                };
+                if repetition.greedy {
+                    quantifier
+                } else {
+                    Node::call("lazy", vec![quantifier])
+                }
+            }
+            Ast::Group(group) => {
+                let inner = self.node(&group.ast);
+                match &group.kind {
+                    GroupKind::CaptureIndex(_) => Node::call("capture", vec![inner]),
+                    GroupKind::CaptureName { name, .. } => {
+                        Node::call("named_capture", vec![inner, Node::Str(name.name.clone())])
+                    }
+                    GroupKind::NonCapturing(flags) => {
+                        let items: Vec<&FlagsItemKind> =
+                            flags.items.iter().map(|item| &item.kind).collect();
+                        let flag = match items.as_slice() {
+                            [] => return inner,
+                            [FlagsItemKind::Flag(Flag::CaseInsensitive)] => "case_insensitive",
+                            [FlagsItemKind::Flag(Flag::MultiLine)] => "multi_line_mode",
+                            [FlagsItemKind::Flag(Flag::DotMatchesNewLine)] => {
+                                "dot_matches_newline_too"
+                            }
+                            [FlagsItemKind::Negation, FlagsItemKind::Flag(Flag::Unicode)] => {
+                                "disable_unicode"
+                            }
+                            _ => return raw(self.text(&group.span)),
+                        };
+                        Node::call(flag, vec![inner])
+                    }
+                }
+            }
+            Ast::Alternation(alternation)
+                if alternation
+                    .asts
+                    .iter()
+                    .any(|option| matches!(option, Ast::Flags(_))) =>
+            {
+                raw(self.text(&alternation.span))
+            }
+            Ast::Alternation(alternation) => Node::call(
+                "or",
+                vec![Node::List(
+                    alternation
+                        .asts
+                        .iter()
+                        .map(|option| self.node(option))
+                        .collect(),
+                )],
+            ),
+            // Flags set in the middle of an expression apply up to the end of the enclosing group,
+            // which would change if the parts were built separately
+            Ast::Concat(concat) if concat.asts.iter().any(|part| matches!(part, Ast::Flags(_))) => {
+                raw(self.text(&concat.span))
+            }
+            Ast::Concat(concat) => {
+                let mut parts = vec![];
+                let mut literal = String::new();
+                for part in &concat.asts {
+                    if let Ast::Literal(c) = part {
+                        literal.push(c.c);
+                        continue;
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Node::call("text", vec![Node::Str(literal.clone())]));
+                        literal.clear();
+                    }
+                    match self.node(part) {
+                        Node::Call { call, args } if call == "concat" => parts.extend(args),
+                        node => parts.push(node),
+                    }
+                }
+                if !literal.is_empty() {
+                    parts.push(Node::call("text", vec![Node::Str(literal)]));
+                }
+                match parts.len() {
+                    1 => parts.remove(0),
+                    _ => Node::call("concat", parts),
+                }
+            }
+            Ast::Flags(flags) => raw(self.text(&flags.span)),
+        }
+    }
+}
+
+/// Whether a group is a plain non-capturing group without flags
+fn is_plain(kind: &GroupKind) -> bool {
+    matches!(kind, GroupKind::NonCapturing(flags) if flags.items.is_empty())
+}
+
+/// Convert a Perl class such as `\d`
+fn perl(kind: &ClassPerlKind, negated: bool) -> Node {
+    nullary(match (kind, negated) {
+        (ClassPerlKind::Digit, false) => "digit",
+        (ClassPerlKind::Digit, true) => "non_digit",
+        (ClassPerlKind::Space, false) => "whitespace",
+        (ClassPerlKind::Space, true) => "non_whitespace",
+        (ClassPerlKind::Word, false) => "word",
+        (ClassPerlKind::Word, true) => "non_word",
+    })
+}
+
+/// Convert a Unicode class such as `\p{Greek}`, if it is one that [unicode_category](crate::unicode_category)
+/// or [unicode_script](crate::unicode_script) produces
+fn unicode(class: &ast::ClassUnicode) -> Option<Node> {
+    let ClassUnicodeKind::Named(name) = &class.kind else {
+        return None;
+    };
+    let variant = name.replace('_', "");
+    let rendered = format!("\\p{{{}}}", name);
+    let builder = if variant
+        .parse::<UnicodeCategory>()
+        .is_ok_and(|category| super::unicode_category(category).0 == rendered)
+    {
+        "unicode_category"
+    } else if variant
+        .parse::<UnicodeScript>()
+        .is_ok_and(|script| super::unicode_script(script).0 == rendered)
+    {
+        "unicode_script"
+    } else {
+        return None;
+    };
+    Some(Node::call(
+        &if class.negated {
+            format!("non_{}", builder)
+        } else {
+            builder.to_string()
+        },
+        vec![Node::Str(variant)],
+    ))
+}
+
+/// Characters that cannot be placed in a set or range without escaping
+fn is_plain_in_class(c: char) -> bool {
+    !matches!(c, '\\' | '[' | ']' | '^' | '-' | '&' | '~') && !c.is_whitespace()
+}
+
+/// Convert a bracketed class such as `[a-z]`, `[[:alpha:]]` or `[\d&&[^4]]`
+fn bracketed(class: &ClassBracketed) -> Option<Node> {
+    let negated = |name: &str| {
+        if class.negated {
+            format!("without_{}", name)
+        } else {
+            format!("within_{}", name)
+        }
+    };
+    Some(match &class.kind {
+        ClassSet::Item(ClassSetItem::Ascii(ascii)) if !class.negated => {
+            let name = match ascii.kind {
+                ClassAsciiKind::Alnum => "alphanumeric",
+                ClassAsciiKind::Alpha => "alphabetic",
+                ClassAsciiKind::Ascii => "ascii",
+                ClassAsciiKind::Blank => "blank",
+                ClassAsciiKind::Cntrl => "control",
+                ClassAsciiKind::Graph => "graphical",
+                ClassAsciiKind::Lower => "lowercase",
+                ClassAsciiKind::Print => "printable",
+                ClassAsciiKind::Punct => "punctuation",
+                ClassAsciiKind::Upper => "uppercase",
+                ClassAsciiKind::Xdigit => "hexdigit",
+                ClassAsciiKind::Digit | ClassAsciiKind::Space | ClassAsciiKind::Word => {
+                    return None
+                }
+            };
+            if ascii.negated {
+                nullary(&format!("non_{}", name))
+            } else {
+                nullary(name)
+            }
+        }
+        ClassSet::Item(ClassSetItem::Range(range))
+            if is_plain_in_class(range.start.c) && is_plain_in_class(range.end.c) =>
+        {
+            Node::call(
+                &negated("range"),
+                vec![Node::Range {
+                    range: (range.start.c, range.end.c),
+                }],
+            )
+        }
+        ClassSet::Item(ClassSetItem::Literal(literal)) if is_plain_in_class(literal.c) => {
+            Node::call(
+                &negated("set"),
+                vec![Node::List(vec![Node::Str(literal.c.to_string())])],
+            )
+        }
+        ClassSet::Item(ClassSetItem::Union(union)) => {
+            let items = union
+                .items
+                .iter()
+                .map(|item| match item {
+                    ClassSetItem::Literal(literal) if is_plain_in_class(literal.c) => {
+                        Some(Node::Str(literal.c.to_string()))
+                    }
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Node::call(&negated("set"), vec![Node::List(items)])
+        }
+        ClassSet::BinaryOp(operation) if !class.negated => {
+            let name = match operation.kind {
+                ClassSetBinaryOpKind::Intersection => "and",
+                ClassSetBinaryOpKind::Difference => "subtract",
+                ClassSetBinaryOpKind::SymmetricDifference => "xor",
+            };
+            Node::call(
+                name,
+                vec![operand(&operation.lhs)?, operand(&operation.rhs)?],
+            )
+        }
+        _ => return None,
+    })
+}
+
+/// Convert one side of a class operation such as `&&`
+fn operand(set: &ClassSet) -> Option<Node> {
+    match set {
+        ClassSet::Item(ClassSetItem::Bracketed(class)) => bracketed(class),
+        ClassSet::Item(ClassSetItem::Perl(class)) => Some(perl(&class.kind, class.negated)),
+        ClassSet::Item(ClassSetItem::Unicode(class)) => unicode(class),
+        _ => None,
+    }
+}
@@ -0,0 +1,432 @@
+//! Functions for emulating backreferences and lookaround, which the regex crate does not support
+//!
+//! Each function adds an empty marker to the regex string that records a condition to check at that
+//! position. [HumanRegex::to_verified_regex] compiles the pattern into a [VerifiedRegex], which
+//! finds candidate matches with an ordinary [Regex] and keeps only the ones that pass every check.
+//! ```
+//! use human_regex::{not_followed_by, one_or_more, digit, text, word_boundary};
+//! let regex_string = word_boundary() + one_or_more(digit()) + not_followed_by(text("px"));
+//! let verified = regex_string.to_verified_regex();
+//! let sizes: Vec<&str> = verified.find_iter("12px 7em 30").map(|m| m.as_str()).collect();
+//! assert_eq!(sizes, vec!["7", "30"]);
+//! ```
+//!
+//! # Trade-offs
+//!
+//! The checks run after the regex crate has found a candidate, so they keep its linear-time
+//! matching but not the full behaviour of a backtracking engine:
+//!
+//! - When a candidate fails a check, the search starts again one character after the start of that
+//!   candidate. Other candidates that begin at the same position, such as shorter repetitions, are
+//!   never tried, and a haystack full of failing candidates takes quadratic time to search.
+//! - Lookaround conditions are matched against the text before or after the marker on its own, so
+//!   anchors and word boundaries inside them see the edge of that text.
+//! - Markers are capturing groups and take up group numbers; prefer [named_capture](crate::named_capture)
+//!   alongside them. A marker inside a repetition only checks the last repetition.
+//! - [HumanRegex::to_regex] ignores the markers entirely, and a [same_as] marker matches only the
+//!   empty string there.
+
+use super::humanregex::*;
+use regex::{Captures, Match, Regex};
+use regex_syntax::ast::{self, Ast, GroupKind};
+use std::marker::PhantomData as pd;
+
+/// The start of every marker: an empty capture group, followed by an alternative that never matches
+/// and that holds the name of the check and its argument
+const MARKER: &str = r"(?:()|[^\s\S]";
+
+/// Build a marker for a check and its argument
+fn marker(check: &str, argument: &str) -> HumanRegex<SymbolChain> {
+    HumanRegex(
+        format!("{}{}(?:{}))", MARKER, check, argument),
+        pd::<SymbolChain>,
+    )
+}
+
+/// Matches the same text as the named capture group, like a backreference
+/// ```
+/// use human_regex::{named_capture, one_or_more, same_as, text, word, word_boundary};
+/// let regex_string = word_boundary()
+///     + named_capture(one_or_more(word()), "word")
+///     + text(" ")
+///     + same_as("word")
+///     + word_boundary();
+/// let verified = regex_string.to_verified_regex();
+/// assert_eq!(verified.find("it is is a typo").unwrap().as_str(), "is is");
+/// assert!(!verified.is_match("it is a typo"));
+/// ```
+pub fn same_as(name: &str) -> HumanRegex<SymbolChain> {
+    marker("same_as", &regex::escape(name))
+}
+
+/// Requires the text after this point to start with a match of the target, without consuming it
+/// ```
+/// use human_regex::{followed_by, one_or_more, digit, text};
+/// let regex_string = one_or_more(digit()) + followed_by(text("px"));
+/// assert_eq!(regex_string.to_verified_regex().find("width: 12px").unwrap().as_str(), "12");
+/// ```
+pub fn followed_by<T>(target: HumanRegex<T>) -> HumanRegex<SymbolChain> {
+    marker("followed_by", &target.0)
+}
+
+/// Requires the text after this point not to start with a match of the target
+/// ```
+/// use human_regex::{not_followed_by, one_or_more, digit, text, word_boundary};
+/// let regex_string = word_boundary() + one_or_more(digit()) + not_followed_by(text("px"));
+/// assert!(!regex_string.to_verified_regex().is_match("12px"));
+/// assert!(regex_string.to_verified_regex().is_match("12em"));
+/// ```
+pub fn not_followed_by<T>(target: HumanRegex<T>) -> HumanRegex<SymbolChain> {
+    marker("not_followed_by", &target.0)
+}
+
+/// Requires the text before this point to end with a match of the target
+/// ```
+/// use human_regex::{preceded_by, one_or_more, digit, text};
+/// let regex_string = preceded_by(text("$")) + one_or_more(digit());
+/// assert_eq!(regex_string.to_verified_regex().find("costs $40").unwrap().as_str(), "40");
+/// ```
+pub fn preceded_by<T>(target: HumanRegex<T>) -> HumanRegex<SymbolChain> {
+    marker("preceded_by", &target.0)
+}
+
+/// Requires the text before this point not to end with a match of the target
+/// ```
+/// use human_regex::{not_preceded_by, one_or_more, digit, text, word_boundary};
+/// let regex_string = not_preceded_by(text("$")) + word_boundary() + one_or_more(digit());
+/// assert_eq!(regex_string.to_verified_regex().find("$40 or 50").unwrap().as_str(), "50");
+/// ```
+pub fn not_preceded_by<T>(target: HumanRegex<T>) -> HumanRegex<SymbolChain> {
+    marker("not_preceded_by", &target.0)
+}
+
+impl<T> HumanRegex<T> {
+    /// Convert to a [VerifiedRegex], which applies the checks added by [same_as], [followed_by],
+    /// [not_followed_by], [preceded_by] and [not_preceded_by]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the regex is invalid or a [same_as] refers to a group that does not exist.
+    pub fn to_verified_regex(&self) -> VerifiedRegex {
+        let parsed = ast::parse::Parser::new().parse(&self.0).unwrap();
+        let mut markers = Vec::new();
+        collect_markers(&parsed, &self.0, &mut markers);
+
+        let mut regex_string = self.0.clone();
+        let mut checks = Vec::new();
+        // Replace from the end of the pattern so that earlier spans stay valid
+        for found in markers.into_iter().rev() {
+            let condition = match found.check.as_str() {
+                "same_as" => {
+                    let name = literal_text(&found.argument)
+                        .expect("same_as takes the name of a capture group");
+                    let referenced = find_named_group(&parsed, &name)
+                        .unwrap_or_else(|| panic!("there is no capture group named `{}`", name));
+                    let mut copy = String::new();
+                    ast::print::Printer::new()
+                        .print(&uncaptured(referenced), &mut copy)
+                        .unwrap();
+                    regex_string.replace_range(found.group_span, &format!("({})", copy));
+                    Condition::SameAs(name)
+                }
+                check => {
+                    let argument = &self.0
+                        [found.argument.span().start.offset..found.argument.span().end.offset];
+                    let (pattern, negated) = match check {
+                        "followed_by" => (format!("^(?:{})", argument), false),
+                        "not_followed_by" => (format!("^(?:{})", argument), true),
+                        "preceded_by" => (format!("(?:{})$", argument), false),
+                        _ => (format!("(?:{})$", argument), true),
+                    };
+                    let regex = Regex::new(&pattern).unwrap();
+                    if check.ends_with("followed_by") {
+                        Condition::After(regex, negated)
+                    } else {
+                        Condition::Before(regex, negated)
+                    }
+                }
+            };
+            checks.push(Check {
+                group: found.group,
+                condition,
+            });
+        }
+        VerifiedRegex {
+            regex: Regex::new(&regex_string).unwrap(),
+            checks,
+            validators: Vec::new(),
+        }
+    }
+}
+
+/// A marker found in a parsed regex
+struct Marker {
+    /// The index of the empty capture group that records the marker's position
+    group: usize,
+    /// The location of that group in the regex string
+    group_span: std::ops::Range<usize>,
+    /// The name of the check
+    check: String,
+    /// The argument of the check
+    argument: Ast,
+}
+
+/// Find the markers in a parsed regex, in the order they appear
+fn collect_markers(node: &Ast, pattern: &str, markers: &mut Vec<Marker>) {
+    match node {
+        Ast::Group(group) => match as_marker(group, pattern) {
+            Some(found) => markers.push(found),
+            None => collect_markers(&group.ast, pattern, markers),
+        },
+        Ast::Repetition(repetition) => collect_markers(&repetition.ast, pattern, markers),
+        Ast::Alternation(alternation) => alternation
+            .asts
+            .iter()
+            .for_each(|option| collect_markers(option, pattern, markers)),
+        Ast::Concat(concat) => concat
+            .asts
+            .iter()
+            .for_each(|part| collect_markers(part, pattern, markers)),
+        _ => {}
+    }
+}
+
+/// Recognize a group written by [marker]
+fn as_marker(group: &ast::Group, pattern: &str) -> Option<Marker> {
+    if !pattern[group.span.start.offset..].starts_with(MARKER) {
+        return None;
+    }
+    let Ast::Alternation(alternation) = &*group.ast else {
+        return None;
+    };
+    let [Ast::Group(position), Ast::Concat(concat)] = alternation.asts.as_slice() else {
+        return None;
+    };
+    let GroupKind::CaptureIndex(index) = position.kind else {
+        return None;
+    };
+    let (Some(Ast::Group(argument)), Some(check)) = (
+        concat.asts.last(),
+        concat.asts[1..concat.asts.len().max(1) - 1]
+            .iter()
+            .map(literal_text)
+            .collect::<Option<String>>(),
+    ) else {
+        return None;
+    };
+    Some(Marker {
+        group: index as usize,
+        group_span: position.span.start.offset..position.span.end.offset,
+        check,
+        argument: (*argument.ast).clone(),
+    })
+}
+
+/// The text matched by a regex made only of literals, if that is what it is
+fn literal_text(node: &Ast) -> Option<String> {
+    match node {
+        Ast::Literal(literal) => Some(literal.c.to_string()),
+        Ast::Concat(concat) => concat.asts.iter().map(literal_text).collect(),
+        _ => None,
+    }
+}
+
+/// Find the contents of the capture group with a given name
+fn find_named_group<'a>(node: &'a Ast, name: &str) -> Option<&'a Ast> {
+    match node {
+        Ast::Group(group) => match &group.kind {
+            GroupKind::CaptureName { name: found, .. } if found.name == name => Some(&group.ast),
+            _ => find_named_group(&group.ast, name),
+        },
+        Ast::Repetition(repetition) => find_named_group(&repetition.ast, name),
+        Ast::Alternation(alternation) => alternation
+            .asts
+            .iter()
+            .find_map(|option| find_named_group(option, name)),
+        Ast::Concat(concat) => concat
+            .asts
+            .iter()
+            .find_map(|part| find_named_group(part, name)),
+        _ => None,
+    }
+}
+
+/// A copy of a regex in which every capture group is non-capturing, so it can be repeated
+fn uncaptured(node: &Ast) -> Ast {
+    match node {
+        Ast::Group(group) => {
+            let mut group = group.clone();
+            if !matches!(group.kind, GroupKind::NonCapturing(_)) {
+                group.kind = GroupKind::NonCapturing(ast::Flags {
+                    span: group.span,
+                    items: vec![],
+                });
+            }
+            group.ast = Box::new(uncaptured(&group.ast));
+            Ast::Group(group)
+        }
+        Ast::Repetition(repetition) => {
+            let mut repetition = repetition.clone();
+            repetition.ast = Box::new(uncaptured(&repetition.ast));
+            Ast::Repetition(repetition)
+        }
+        Ast::Alternation(alternation) => {
+            let mut alternation = alternation.clone();
+            alternation.asts = alternation.asts.iter().map(uncaptured).collect();
+            Ast::Alternation(alternation)
+        }
+        Ast::Concat(concat) => {
+            let mut concat = concat.clone();
+            concat.asts = concat.asts.iter().map(uncaptured).collect();
+            Ast::Concat(concat)
+        }
+        other => other.clone(),
+    }
+}
+
+/// A condition checked at the position of a marker
+#[derive(Debug, Clone)]
+enum Condition {
+    /// The marker group must match the same text as the named group
+    SameAs(String),
+    /// The text after the marker must (or, when negated, must not) match the regex
+    After(Regex, bool),
+    /// The text before the marker must (or, when negated, must not) match the regex
+    Before(Regex, bool),
+}
+
+/// A condition and the capture group whose position it is checked at
+#[derive(Debug, Clone)]
+struct Check {
+    /// The index of the marker's capture group
+    group: usize,
+    /// What to check
+    condition: Condition,
+}
+
+/// A compiled regex that filters its matches with the checks added by [same_as], [followed_by],
+/// [not_followed_by], [preceded_by] and [not_preceded_by]. See the [module documentation](self)
+/// for how this differs from a backtracking engine.
+#[derive(Debug, Clone)]
+pub struct VerifiedRegex {
+    /// The regex that finds candidate matches
+    regex: Regex,
+    /// The checks every match has to pass
+    checks: Vec<Check>,
+    /// The functions the text of every match has to pass
+    validators: Vec<fn(&str) -> bool>,
+}
+
+impl VerifiedRegex {
+    /// Also require the text of every match to pass a function, for conditions that no regex can
+    /// express, such as check digits
+    /// ```
+    /// use human_regex::{digit, exactly, word_boundary};
+    /// let even = (word_boundary() + exactly(2, digit()) + word_boundary())
+    ///     .to_verified_regex()
+    ///     .with_validator(|found| found.parse::<u8>().unwrap() % 2 == 0);
+    /// let found: Vec<&str> = even.find_iter("13 42 77 08").map(|m| m.as_str()).collect();
+    /// assert_eq!(found, vec!["42", "08"]);
+    /// ```
+    pub fn with_validator(mut self, validator: fn(&str) -> bool) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// The regex that finds candidate matches, before any checks
+    pub fn candidate_regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    /// Whether there is a match anywhere in the haystack
+    pub fn is_match(&self, haystack: &str) -> bool {
+        self.captures(haystack).is_some()
+    }
+
+    /// The leftmost match in the haystack
+    pub fn find<'h>(&self, haystack: &'h str) -> Option<Match<'h>> {
+        self.captures(haystack).and_then(|captures| captures.get(0))
+    }
+
+    /// The capture groups of the leftmost match in the haystack
+    pub fn captures<'h>(&self, haystack: &'h str) -> Option<Captures<'h>> {
+        self.captures_at(haystack, 0)
+    }
+
+    /// The capture groups of the leftmost match that starts at or after `start`
+    pub fn captures_at<'h>(&self, haystack: &'h str, start: usize) -> Option<Captures<'h>> {
+        let mut start = start;
+        loop {
+            let captures = self.regex.captures_at(haystack, start)?;
+            if self.verify(haystack, &captures) {
+                return Some(captures);
+            }
+            let candidate = captures.get(0).unwrap().start();
+            start = candidate + haystack[candidate..].chars().next()?.len_utf8();
+        }
+    }
+
+    /// All successive non-overlapping matches in the haystack
+    pub fn find_iter<'r, 'h>(&'r self, haystack: &'h str) -> impl Iterator<Item = Match<'h>> + 'r
+    where
+        'h: 'r,
+    {
+        self.captures_iter(haystack)
+            .map(|captures| captures.get(0).unwrap())
+    }
+
+    /// The capture groups of all successive non-overlapping matches in the haystack
+    pub fn captures_iter<'r, 'h>(
+        &'r self,
+        haystack: &'h str,
+    ) -> impl Iterator<Item = Captures<'h>> + 'r
+    where
+        'h: 'r,
+    {
+        let mut start = Some(0);
+        let mut last_end = None;
+        std::iter::from_fn(move || loop {
+            let captures = self.captures_at(haystack, start?)?;
+            let whole = captures.get(0).unwrap();
+            // Like the regex crate, skip an empty match right after the previous match
+            start = if whole.is_empty() {
+                haystack[whole.end()..]
+                    .chars()
+                    .next()
+                    .map(|c| whole.end() + c.len_utf8())
+            } else {
+                Some(whole.end())
+            };
+            if whole.is_empty() && last_end == Some(whole.end()) {
+                continue;
+            }
+            last_end = Some(whole.end());
+            return Some(captures);
+        })
+    }
+
+    /// Whether a candidate match passes every check and validator
+    fn verify(&self, haystack: &str, captures: &Captures) -> bool {
+        let whole = captures.get(0).unwrap().as_str();
+        if !self.validators.iter().all(|validator| validator(whole)) {
+            return false;
+        }
+        self.checks.iter().all(|check| {
+            // A marker in a branch that was not taken does not apply
+            let Some(position) = captures.get(check.group) else {
+                return true;
+            };
+            match &check.condition {
+                Condition::SameAs(name) => captures
+                    .name(name)
+                    .is_some_and(|referenced| referenced.as_str() == position.as_str()),
+                Condition::After(regex, negated) => {
+                    regex.is_match(&haystack[position.start()..]) != *negated
+                }
+                Condition::Before(regex, negated) => {
+                    regex.is_match(&haystack[..position.start()]) != *negated
+                }
+            }
+        })
+    }
+}